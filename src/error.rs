@@ -13,6 +13,100 @@ pub enum InstanceError {
     #[error("Missing Vulkan layers {0:?}")]
     MissingLayers(Vec<String>),
 
+    #[error("Missing Vulkan device extensions {0:?}")]
+    MissingDeviceExtensions(Vec<String>),
+
+    #[error("Missing Vulkan device features {0:?}")]
+    MissingDeviceFeatures(Vec<String>),
+
+    #[error("No Vulkan physical devices are available")]
+    NoPhysicalDevices,
+
+    #[error(
+        "No physical device has a compute queue and the required features"
+    )]
+    NoComputeDevice,
+
+    #[error("No physical device can render and present to the surface")]
+    NoGraphicsDevice,
+
+    #[error("No physical device satisfies the requirements: {0:?}")]
+    NoSuitableDevice(Vec<String>),
+
+    #[error("Queue family {0} only supports {1} queues")]
+    TooManyQueues(u32, u32),
+
+    #[error("Queue priority {1} for family {0} is not between 0.0 and 1.0")]
+    InvalidQueuePriority(u32, f32),
+
+    #[error("The physical device is not a member of the device group")]
+    PhysicalDeviceNotInGroup,
+
+    #[error("Device groups require Vulkan 1.1")]
+    DeviceGroupsNotSupported,
+
+    #[error("Protected queues require the protected_memory feature")]
+    ProtectedMemoryNotEnabled,
+
+    #[error("{0:?} cannot be used as a name because it contains a nul byte")]
+    InvalidName(String),
+
+    #[error("The DebugUtils extension is not loaded")]
+    DebugUtilsNotLoaded,
+
+    #[error("The Vulkan device was lost")]
+    DeviceLost,
+
     #[error("Unexpected Vulkan error! {0:?}")]
-    UnexpectedVulkanError(#[from] vk::Result),
+    UnexpectedVulkanError(vk::Result),
+}
+
+impl From<vk::Result> for InstanceError {
+    /// Convert a raw Vulkan error.
+    ///
+    /// ERROR_DEVICE_LOST becomes [InstanceError::DeviceLost] so applications
+    /// can match on it directly, e.g. to re-enumerate devices after a GPU is
+    /// removed or the driver resets.
+    fn from(result: vk::Result) -> Self {
+        match result {
+            vk::Result::ERROR_DEVICE_LOST => InstanceError::DeviceLost,
+            _ => InstanceError::UnexpectedVulkanError(result),
+        }
+    }
+}
+
+impl InstanceError {
+    /// Returns true when retrying the failed operation might succeed.
+    ///
+    /// Transient conditions like a lost device, a timeout, or memory pressure
+    /// are recoverable. Problems with the environment or the application's
+    /// configuration, like a missing loader or missing extensions, are fatal.
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            InstanceError::CannotLoadVulkanEntry(_) => false,
+            InstanceError::MissingExtensions(_) => false,
+            InstanceError::MissingLayers(_) => false,
+            InstanceError::MissingDeviceExtensions(_) => false,
+            InstanceError::MissingDeviceFeatures(_) => false,
+            InstanceError::NoPhysicalDevices => false,
+            InstanceError::NoComputeDevice => false,
+            InstanceError::NoGraphicsDevice => false,
+            InstanceError::NoSuitableDevice(_) => false,
+            InstanceError::TooManyQueues(_, _) => false,
+            InstanceError::InvalidQueuePriority(_, _) => false,
+            InstanceError::PhysicalDeviceNotInGroup => false,
+            InstanceError::DeviceGroupsNotSupported => false,
+            InstanceError::ProtectedMemoryNotEnabled => false,
+            InstanceError::InvalidName(_) => false,
+            InstanceError::DebugUtilsNotLoaded => false,
+            InstanceError::DeviceLost => true,
+            InstanceError::UnexpectedVulkanError(result) => matches!(
+                *result,
+                vk::Result::TIMEOUT
+                    | vk::Result::NOT_READY
+                    | vk::Result::ERROR_OUT_OF_HOST_MEMORY
+                    | vk::Result::ERROR_OUT_OF_DEVICE_MEMORY
+            ),
+        }
+    }
 }
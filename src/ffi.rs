@@ -1,6 +1,9 @@
-use std::{
-    ffi::{c_char, CString},
-    string::FromUtf8Error,
+use {
+    ash::vk,
+    std::{
+        ffi::{c_char, CStr, CString},
+        string::FromUtf8Error,
+    },
 };
 
 /// Build a vector of CStrings and a matching vector of pointers to those
@@ -40,3 +43,40 @@ pub fn string_from_i8(bytes: &[i8]) -> Result<String, FromUtf8Error> {
             .collect(),
     )
 }
+
+/// Build owned Strings from a slice of C strings.
+///
+/// This is convenient for the name constants provided by Ash, e.g.
+/// `Swapchain::name()`. Any invalid utf-8 is replaced.
+pub fn cstrs_to_strings(cstrs: &[&CStr]) -> Vec<String> {
+    cstrs
+        .iter()
+        .map(|cstr| cstr.to_string_lossy().into_owned())
+        .collect()
+}
+
+/// Call a Vulkan query which reports a count and then fills an array, like
+/// vkGetPhysicalDeviceFragmentShadingRatesKHR.
+///
+/// The query is repeated if it reports INCOMPLETE because the count grew
+/// between the two calls.
+///
+/// # Safety
+///
+/// Unsafe because `query` must write at most `count` values to the pointer
+/// it's given, and only when the pointer is not null.
+pub unsafe fn enumerate<T: Default + Clone>(
+    mut query: impl FnMut(&mut u32, *mut T) -> vk::Result,
+) -> Result<Vec<T>, vk::Result> {
+    loop {
+        let mut count = 0;
+        query(&mut count, std::ptr::null_mut()).result()?;
+        let mut values = vec![T::default(); count as usize];
+        let result = query(&mut count, values.as_mut_ptr());
+        if result != vk::Result::INCOMPLETE {
+            result.result()?;
+            values.truncate(count as usize);
+            return Ok(values);
+        }
+    }
+}
@@ -29,28 +29,19 @@
 //!     // Find a device which has at least one queue family that supports
 //!     // compute operations.
 //!     device
-//!         .queue_family_properties()
-//!         .iter()
-//!         .any(|family_properties| {
-//!             family_properties
-//!                 .queue_flags
-//!                 .contains(vk::QueueFlags::COMPUTE)
-//!         })
+//!         .queue_families_with_flags(vk::QueueFlags::COMPUTE)
+//!         .next()
+//!         .is_some()
 //! })
 //! .unwrap();
 //!
-//! let compute_queue_index = physical_device
-//!     .queue_family_properties()
-//!     .iter()
-//!     .enumerate()
-//!     .find(|(_, properties)| {
-//!         properties.queue_flags.contains(vk::QueueFlags::COMPUTE)
-//!     })
-//!     .map(|(queue_family_index, _)| queue_family_index)
+//! let (compute_queue_index, _) = physical_device
+//!     .queue_families_with_flags(vk::QueueFlags::COMPUTE)
+//!     .next()
 //!     .unwrap();
 //!
-//! let mut family_info = QueueFamilyInfo::new(compute_queue_index as u32);
-//! family_info.add_queue_priority(1.0);
+//! let family_info =
+//!     QueueFamilyInfo::from_priorities(compute_queue_index, [1.0]).unwrap();
 //!
 //! let mut logical_device = unsafe {
 //!     LogicalDevice::new(&instance, physical_device, &[], &[family_info])
@@ -60,6 +51,9 @@
 //! log::info!("Created Logical device! {}", logical_device);
 //! ```
 
+#[macro_use]
+mod logging;
+
 mod error;
 mod ffi;
 mod logical_device;
@@ -68,11 +62,18 @@ mod vulkan_instance;
 
 pub use self::{
     error::{InstanceError, InstanceResult},
-    logical_device::{LogicalDevice, QueueFamilyInfo},
+    logical_device::{DeviceBuilder, LogicalDevice, QueueFamilyInfo},
     physical_device::{
-        PhysicalDevice, PhysicalDeviceFeatures, PhysicalDeviceProperties,
+        DeviceRequirements, FeatureDiff, PhysicalDevice,
+        PhysicalDeviceFeatures, PhysicalDeviceGroup, PhysicalDeviceInfo,
+        PhysicalDeviceProperties, RejectReason,
+    },
+    vulkan_instance::{
+        DebugMessageFormat, DebugScope, DiscardValidationSink,
+        LogValidationSink, ValidationErrorGuard, ValidationMessage,
+        ValidationObject, ValidationSink, VulkanInstance,
+        VulkanInstanceBuilder,
     },
-    vulkan_instance::VulkanInstance,
 };
 
 /// Types which implement this trait can provide the raw Vulkan resource handle
@@ -80,6 +81,10 @@ pub use self::{
 pub trait VulkanHandle {
     type Handle;
 
+    /// The Vulkan object type for the raw handle. This is used to give the
+    /// handle a debug name with the DebugUtils extension.
+    const OBJECT_TYPE: ash::vk::ObjectType;
+
     /// Get the raw resource handle.
     ///
     /// # Safety
@@ -88,4 +93,12 @@ pub trait VulkanHandle {
     /// handle. The caller is responsible for ensuring no copies of the handle
     /// outlive the original instance.
     unsafe fn raw(&self) -> &Self::Handle;
+
+    /// Get the raw resource handle as the untyped u64 value used by the
+    /// DebugUtils extension.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe for the same reasons as [VulkanHandle::raw].
+    unsafe fn raw_object_handle(&self) -> u64;
 }
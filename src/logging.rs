@@ -0,0 +1,38 @@
+//! Internal logging macros.
+//!
+//! These forward to the log facade when the `logging` feature is enabled and
+//! compile to nothing otherwise. The arguments are still type-checked either
+//! way so disabling the feature can't hide broken log statements.
+
+macro_rules! forward_to_log {
+    ($level:ident, $($arg:tt)*) => {{
+        #[cfg(feature = "logging")]
+        {
+            log::$level!($($arg)*);
+        }
+        #[cfg(not(feature = "logging"))]
+        {
+            let _ = format_args!($($arg)*);
+        }
+    }};
+}
+
+macro_rules! log_trace {
+    ($($arg:tt)*) => { forward_to_log!(trace, $($arg)*) };
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)*) => { forward_to_log!(debug, $($arg)*) };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)*) => { forward_to_log!(info, $($arg)*) };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => { forward_to_log!(warn, $($arg)*) };
+}
+
+macro_rules! log_error {
+    ($($arg:tt)*) => { forward_to_log!(error, $($arg)*) };
+}
@@ -0,0 +1,237 @@
+use {
+    crate::{
+        DeviceRequirements, InstanceError, InstanceResult, LogicalDevice,
+        PhysicalDevice, PhysicalDeviceFeatures, QueueFamilyInfo,
+        VulkanInstance,
+    },
+    ash::vk,
+};
+
+/// Select a physical device and create a logical device with its queues in a
+/// single step.
+///
+/// The builder enumerates every physical device, keeps the ones which satisfy
+/// the requirements and have room for every requested queue, prefers discrete
+/// GPUs, and creates the logical device. Extensions needed by the requested
+/// features are enabled automatically.
+///
+/// # Examples
+///
+/// ```
+/// use {
+///     ash::vk,
+///     ccthw_ash_instance::{DeviceBuilder, VulkanInstance},
+/// };
+///
+/// let instance = unsafe { VulkanInstance::new(&[], &[]).unwrap() };
+/// let (logical_device, queues) = unsafe {
+///     DeviceBuilder::new(&instance)
+///         .queue(vk::QueueFlags::COMPUTE)
+///         .build()
+///         .unwrap()
+/// };
+/// ```
+pub struct DeviceBuilder<'a> {
+    instance: &'a VulkanInstance,
+    requirements: DeviceRequirements,
+    queue_requests: Vec<vk::QueueFlags>,
+    frames_in_flight: u32,
+}
+
+/// A physical device which satisfies the builder's requirements, along with
+/// the queue family and queue index assigned to each requested queue.
+struct Candidate {
+    physical_device: PhysicalDevice,
+    queue_assignments: Vec<(u32, u32)>,
+}
+
+impl<'a> DeviceBuilder<'a> {
+    /// Create a builder with no requirements and no requested queues.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical devices.
+    pub fn new(instance: &'a VulkanInstance) -> Self {
+        Self {
+            instance,
+            requirements: DeviceRequirements::new(),
+            queue_requests: vec![],
+            frames_in_flight: LogicalDevice::DEFAULT_FRAMES_IN_FLIGHT,
+        }
+    }
+
+    /// Replace all of the device requirements.
+    pub fn requirements(mut self, requirements: DeviceRequirements) -> Self {
+        self.requirements = requirements;
+        self
+    }
+
+    /// Require and enable every feature which is enabled in `features`.
+    ///
+    /// Features accumulate, see [DeviceRequirements::features].
+    pub fn features(mut self, features: PhysicalDeviceFeatures) -> Self {
+        self.requirements = self.requirements.features(features);
+        self
+    }
+
+    /// Require and enable a device extension.
+    pub fn extension(mut self, name: impl Into<String>) -> Self {
+        self.requirements = self.requirements.extension(name);
+        self
+    }
+
+    /// Request a queue with priority 1.0 from a family which supports all of
+    /// `flags`.
+    ///
+    /// Queues are returned by [DeviceBuilder::build] in the order they were
+    /// requested. Requests which share a family get separate queues, so the
+    /// family must have room for all of them.
+    pub fn queue(mut self, flags: vk::QueueFlags) -> Self {
+        self.queue_requests.push(flags);
+        self
+    }
+
+    /// Set the frames-in-flight hint stored on the logical device, see
+    /// [LogicalDevice::frames_in_flight].
+    pub fn frames_in_flight(mut self, frames_in_flight: u32) -> Self {
+        self.frames_in_flight = frames_in_flight;
+        self
+    }
+
+    /// Select a physical device and create the logical device.
+    ///
+    /// A single queue from a family which supports the requirement's queue
+    /// flags is created when no queues were requested.
+    ///
+    /// # Returns
+    ///
+    /// The logical device and one queue for each request, or:
+    ///
+    /// * [InstanceError::NoPhysicalDevices] if the system has no Vulkan
+    ///   devices.
+    /// * [InstanceError::NoSuitableDevice] listing why each device was
+    ///   rejected, e.g. missing features or missing extensions.
+    /// * Any error from [LogicalDevice::new].
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the logical device must be dropped before the instance
+    /// used to create it.
+    pub unsafe fn build(
+        self,
+    ) -> InstanceResult<(LogicalDevice, Vec<vk::Queue>)> {
+        let queue_requests = if self.queue_requests.is_empty() {
+            vec![self.requirements.required_queue_flags()]
+        } else {
+            self.queue_requests.clone()
+        };
+
+        let mut rejections = vec![];
+        let mut candidates = vec![];
+        for physical_device in PhysicalDevice::enumerate_all(self.instance)? {
+            let mut unmet =
+                self.requirements.unmet_requirements(&physical_device);
+            let queue_assignments =
+                assign_queues(&physical_device, &queue_requests);
+            if queue_assignments.is_none() {
+                unmet.push(format!(
+                    "no room for the requested queues {:?}",
+                    queue_requests
+                ));
+            }
+            match queue_assignments {
+                Some(queue_assignments) if unmet.is_empty() => {
+                    candidates.push(Candidate {
+                        physical_device,
+                        queue_assignments,
+                    })
+                }
+                _ => rejections.extend(unmet.into_iter().map(|reason| {
+                    format!("{}: {}", physical_device.name(), reason)
+                })),
+            }
+        }
+
+        // Discrete GPUs sort first, otherwise enumeration order is kept.
+        candidates.sort_by_key(|candidate| {
+            candidate
+                .physical_device
+                .properties()
+                .properties()
+                .device_type
+                != vk::PhysicalDeviceType::DISCRETE_GPU
+        });
+        let Candidate {
+            physical_device,
+            queue_assignments,
+        } = candidates
+            .into_iter()
+            .next()
+            .ok_or(InstanceError::NoSuitableDevice(rejections))?;
+
+        let mut queue_family_infos: Vec<QueueFamilyInfo> = vec![];
+        for &(queue_family_index, _) in &queue_assignments {
+            match queue_family_infos
+                .iter_mut()
+                .find(|info| info.queue_family_index() == queue_family_index)
+            {
+                Some(info) => info.add_queue_priority(1.0),
+                None => {
+                    let mut info = QueueFamilyInfo::new(queue_family_index);
+                    info.add_queue_priority(1.0);
+                    queue_family_infos.push(info);
+                }
+            }
+        }
+
+        let physical_device = physical_device
+            .with_features(*self.requirements.required_features());
+        let mut logical_device = LogicalDevice::new(
+            self.instance,
+            physical_device,
+            self.requirements.required_extensions(),
+            &queue_family_infos,
+        )?;
+        logical_device.set_frames_in_flight(self.frames_in_flight);
+
+        let queues = queue_assignments
+            .iter()
+            .map(|&(queue_family_index, queue_index)| {
+                // Every assigned queue was created above.
+                logical_device.queue_handles_for_family(queue_family_index)
+                    [queue_index as usize]
+            })
+            .collect();
+
+        Ok((logical_device, queues))
+    }
+}
+
+/// Assign each requested queue to the first family which supports its flags
+/// and still has room for another queue.
+///
+/// # Returns
+///
+/// The `(queue_family_index, queue_index)` for each request, or None if any
+/// request can't be satisfied.
+fn assign_queues(
+    physical_device: &PhysicalDevice,
+    queue_requests: &[vk::QueueFlags],
+) -> Option<Vec<(u32, u32)>> {
+    let mut used_queue_counts =
+        vec![0; physical_device.queue_family_properties().len()];
+    queue_requests
+        .iter()
+        .map(|&flags| {
+            let (queue_family_index, _) = physical_device
+                .queue_families_with_flags(flags)
+                .find(|(index, properties)| {
+                    used_queue_counts[*index as usize] < properties.queue_count
+                })?;
+            let queue_index = used_queue_counts[queue_family_index as usize];
+            used_queue_counts[queue_family_index as usize] += 1;
+            Some((queue_family_index, queue_index))
+        })
+        .collect()
+}
@@ -0,0 +1,84 @@
+use {
+    crate::{InstanceError, InstanceResult, LogicalDevice, VulkanInstance},
+    ash::extensions::khr::{Swapchain, Synchronization2, TimelineSemaphore},
+    std::ffi::CStr,
+};
+
+impl LogicalDevice {
+    /// Create the VK_KHR_swapchain extension loader for this device.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance used to create this device.
+    ///
+    /// # Returns
+    ///
+    /// Returns MissingDeviceExtensions if the extension wasn't enabled when
+    /// creating the device.
+    pub fn swapchain_loader(
+        &self,
+        instance: &VulkanInstance,
+    ) -> InstanceResult<Swapchain> {
+        self.check_extension_enabled(Swapchain::name())?;
+        Ok(Swapchain::new(instance.ash(), &self.device))
+    }
+
+    /// Create the VK_KHR_synchronization2 extension loader for this device.
+    ///
+    /// Devices which support Vulkan 1.3 can use the core functions on the
+    /// raw device instead.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance used to create this device.
+    ///
+    /// # Returns
+    ///
+    /// Returns MissingDeviceExtensions if the extension wasn't enabled when
+    /// creating the device.
+    pub fn synchronization2_loader(
+        &self,
+        instance: &VulkanInstance,
+    ) -> InstanceResult<Synchronization2> {
+        self.check_extension_enabled(Synchronization2::name())?;
+        Ok(Synchronization2::new(instance.ash(), &self.device))
+    }
+
+    /// Create the VK_KHR_timeline_semaphore extension loader for this
+    /// device.
+    ///
+    /// Devices which support Vulkan 1.2 can use the core functions on the
+    /// raw device instead.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance used to create this device.
+    ///
+    /// # Returns
+    ///
+    /// Returns MissingDeviceExtensions if the extension wasn't enabled when
+    /// creating the device.
+    pub fn timeline_semaphore_loader(
+        &self,
+        instance: &VulkanInstance,
+    ) -> InstanceResult<TimelineSemaphore> {
+        self.check_extension_enabled(TimelineSemaphore::name())?;
+        Ok(TimelineSemaphore::new(instance.ash(), &self.device))
+    }
+
+    /// Check that a device extension was enabled when creating the device.
+    fn check_extension_enabled(&self, name: &CStr) -> InstanceResult<()> {
+        let name = name.to_string_lossy();
+        if self
+            .active_physical_device_extensions
+            .iter()
+            .any(|enabled| *enabled == name)
+        {
+            Ok(())
+        } else {
+            Err(InstanceError::MissingDeviceExtensions(vec![
+                name.to_string()
+            ]))
+        }
+    }
+}
@@ -1,14 +1,22 @@
 use {
     crate::{
-        ffi, InstanceResult, PhysicalDevice, VulkanHandle, VulkanInstance,
+        ffi, InstanceError, InstanceResult, PhysicalDevice,
+        PhysicalDeviceFeatures, PhysicalDeviceGroup, VulkanHandle,
+        VulkanInstance,
     },
-    ash::vk,
+    ash::vk::{self, Handle},
     indoc::indoc,
+    std::ffi::CStr,
 };
 
+mod device_builder;
+mod extension_loaders;
+mod presets;
 mod queue_family_info;
 
-pub use self::queue_family_info::QueueFamilyInfo;
+pub use self::{
+    device_builder::DeviceBuilder, queue_family_info::QueueFamilyInfo,
+};
 
 /// The logical device and information about the backing physical device.
 ///
@@ -19,6 +27,10 @@ pub use self::queue_family_info::QueueFamilyInfo;
 pub struct LogicalDevice {
     physical_device: PhysicalDevice,
     active_physical_device_extensions: Vec<String>,
+    active_layers: Vec<String>,
+    enabled_features: PhysicalDeviceFeatures,
+    queues: Vec<(u32, Vec<vk::Queue>)>,
+    frames_in_flight: u32,
     device: ash::Device,
 }
 
@@ -30,6 +42,10 @@ pub struct LogicalDevice {
 unsafe impl Sync for LogicalDevice {}
 
 impl LogicalDevice {
+    /// The frames-in-flight hint used when a device is created without
+    /// setting one explicitly.
+    pub const DEFAULT_FRAMES_IN_FLIGHT: u32 = 2;
+
     /// Get the physical device being controlled by this logical device.
     pub fn physical_device(&self) -> &PhysicalDevice {
         &self.physical_device
@@ -40,6 +56,118 @@ impl LogicalDevice {
         &self.active_physical_device_extensions
     }
 
+    /// Get all of the layers used when creating the device. These are the
+    /// layers which were enabled on the instance.
+    pub fn active_layers(&self) -> &[String] {
+        &self.active_layers
+    }
+
+    /// Get the exact set of features which were enabled when creating the
+    /// device.
+    pub fn enabled_features(&self) -> &PhysicalDeviceFeatures {
+        &self.enabled_features
+    }
+
+    /// Re-query the physical device's features and check that every enabled
+    /// feature is still reported.
+    ///
+    /// This is a sanity check for driver quirks where a feature was
+    /// advertised when the device was picked but is no longer available.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance used to create this device.
+    ///
+    /// # Returns
+    ///
+    /// [InstanceError::MissingDeviceFeatures] listing every enabled feature
+    /// which the physical device no longer reports.
+    pub fn verify_enabled_features(
+        &self,
+        instance: &VulkanInstance,
+    ) -> InstanceResult<()> {
+        let available_features =
+            PhysicalDeviceFeatures::from_physical_device(instance, unsafe {
+                // SAFE because the handle is only used to query features.
+                self.physical_device.raw()
+            });
+        let missing: Vec<String> = self
+            .enabled_features
+            .missing_features(&available_features)
+            .into_iter()
+            .map(str::to_owned)
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(InstanceError::MissingDeviceFeatures(missing))
+        }
+    }
+
+    /// Every queue created in a queue family.
+    ///
+    /// The queues are in the same order as the priorities added to the
+    /// family's [QueueFamilyInfo], so `queue_handles_for_family(i)[0]` has
+    /// the first priority. Infos which shared a family index and flags were
+    /// merged in the order they were provided. This is useful for
+    /// round-robin submission across several queues.
+    ///
+    /// When a family had infos with different flags, e.g. protected and
+    /// unprotected queues, only the queues from the first of those infos are
+    /// returned.
+    ///
+    /// # Params
+    ///
+    /// * `queue_family_index` - the queue family index used when creating
+    ///   the device.
+    ///
+    /// # Returns
+    ///
+    /// An empty slice when no queues were created in the family.
+    pub fn queue_handles_for_family(
+        &self,
+        queue_family_index: u32,
+    ) -> &[vk::Queue] {
+        self.queues
+            .iter()
+            .find(|(index, _)| *index == queue_family_index)
+            .map(|(_, queues)| queues.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// How many frames the application intends to have in flight at once.
+    ///
+    /// This is only a hint which is carried along with the device so
+    /// renderers and swapchain code can size their per-frame resources
+    /// consistently. Nothing in this crate enforces it. Defaults to
+    /// [Self::DEFAULT_FRAMES_IN_FLIGHT].
+    pub fn frames_in_flight(&self) -> u32 {
+        self.frames_in_flight
+    }
+
+    /// Set the frames-in-flight hint.
+    ///
+    /// # Params
+    ///
+    /// * `frames_in_flight` - the number of frames in flight. Values less
+    ///   than 1 are clamped to 1.
+    pub fn set_frames_in_flight(&mut self, frames_in_flight: u32) {
+        self.frames_in_flight = frames_in_flight.max(1);
+    }
+
+    /// Block until all outstanding work on every device queue has completed.
+    ///
+    /// This is typically called before destroying device resources at
+    /// shutdown.
+    pub fn wait_idle(&self) -> InstanceResult<()> {
+        unsafe {
+            // SAFE because the device handle is valid for the lifetime of
+            // self.
+            self.device.device_wait_idle()?
+        };
+        Ok(())
+    }
+
     /// Create a logical device for interfacing with a given physical device.
     ///
     /// # Params
@@ -49,9 +177,19 @@ impl LogicalDevice {
     /// * `physical_device` - the actual physical device which this logical
     ///   device will control.
     /// * `physical_device_extensions` - the extentions to enable for the
-    ///   physical device.
+    ///   physical device. Extensions needed by the requested features, like
+    ///   VK_EXT_robustness2, are added automatically when the device supports
+    ///   them. See [PhysicalDeviceFeatures::required_extensions].
     /// * `queue_family_infos` - a slice of structs which control how many
-    ///   device queues to create.
+    ///   device queues to create. Infos which share a queue family index are
+    ///   merged with [QueueFamilyInfo::merge_by_index].
+    ///
+    /// # Returns
+    ///
+    /// Returns MissingDeviceFeatures if the device or instance predates
+    /// Vulkan 1.1 and features outside of the core Vulkan 1.0 features are
+    /// requested. Those features can't be enabled without
+    /// PhysicalDeviceFeatures2.
     ///
     /// # Safety
     ///
@@ -63,6 +201,118 @@ impl LogicalDevice {
         physical_device_extensions: &[String],
         queue_family_infos: &[QueueFamilyInfo],
     ) -> InstanceResult<Self> {
+        Self::create(
+            instance,
+            physical_device,
+            physical_device_extensions,
+            queue_family_infos,
+            &[],
+        )
+    }
+
+    /// Create a logical device using C string extension names.
+    ///
+    /// This is the same as [LogicalDevice::new], but accepts names like the
+    /// ones provided by Ash's extension loaders, e.g. `Swapchain::name()`.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe for the same reasons as [LogicalDevice::new].
+    pub unsafe fn new_with_cstr_names(
+        instance: &VulkanInstance,
+        physical_device: PhysicalDevice,
+        physical_device_extensions: &[&CStr],
+        queue_family_infos: &[QueueFamilyInfo],
+    ) -> InstanceResult<Self> {
+        Self::new(
+            instance,
+            physical_device,
+            &ffi::cstrs_to_strings(physical_device_extensions),
+            queue_family_infos,
+        )
+    }
+
+    /// Create a logical device which controls every physical device in a
+    /// device group.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the physical
+    ///   devices.
+    /// * `device_group` - the physical devices which this logical device will
+    ///   control.
+    /// * `physical_device` - the group member whose requested features and
+    ///   queue families are used to create the device.
+    /// * `physical_device_extensions` - the extentions to enable for the
+    ///   physical devices.
+    /// * `queue_family_infos` - a slice of structs which control how many
+    ///   device queues to create. Infos which share a queue family index are
+    ///   merged with [QueueFamilyInfo::merge_by_index].
+    ///
+    /// # Returns
+    ///
+    /// Returns PhysicalDeviceNotInGroup if `physical_device` is not a member
+    /// of `device_group`, or DeviceGroupsNotSupported if the device or
+    /// instance predates Vulkan 1.1.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the logical device must be dropped before the instance
+    /// used to create it.
+    pub unsafe fn new_with_device_group(
+        instance: &VulkanInstance,
+        device_group: &PhysicalDeviceGroup,
+        physical_device: PhysicalDevice,
+        physical_device_extensions: &[String],
+        queue_family_infos: &[QueueFamilyInfo],
+    ) -> InstanceResult<Self> {
+        if !device_group.contains(&physical_device) {
+            return Err(InstanceError::PhysicalDeviceNotInGroup);
+        }
+        if physical_device.usable_api_version(instance) < vk::API_VERSION_1_1 {
+            return Err(InstanceError::DeviceGroupsNotSupported);
+        }
+        Self::create(
+            instance,
+            physical_device,
+            physical_device_extensions,
+            queue_family_infos,
+            &device_group.raw_handles(),
+        )
+    }
+
+    /// Create the logical device.
+    ///
+    /// The device group create info is only chained when
+    /// `device_group_handles` is not empty.
+    unsafe fn create(
+        instance: &VulkanInstance,
+        physical_device: PhysicalDevice,
+        physical_device_extensions: &[String],
+        queue_family_infos: &[QueueFamilyInfo],
+        device_group_handles: &[vk::PhysicalDevice],
+    ) -> InstanceResult<Self> {
+        let queue_family_infos =
+            QueueFamilyInfo::merge_by_index(queue_family_infos);
+        let physical_device_extensions = &Self::with_feature_extensions(
+            &physical_device,
+            physical_device_extensions,
+        );
+
+        // Chaining PhysicalDeviceFeatures2 needs Vulkan 1.1 support from both
+        // the instance and the device. Only the core Vulkan 1.0 features can
+        // be enabled otherwise. Likewise, the Vulkan 1.3 features struct is
+        // only chained for Vulkan 1.3.
+        let api_version = physical_device.usable_api_version(instance);
+        let supports_features2 = api_version >= vk::API_VERSION_1_1;
+
+        if !supports_features2 {
+            Self::check_core_features_only(&physical_device)?;
+        }
+        Self::check_extensions(&physical_device, physical_device_extensions)?;
+        Self::check_queue_flags(&physical_device, &queue_family_infos)?;
+        Self::check_queue_counts(&physical_device, &queue_family_infos)?;
+
         let (_c_layer_names, layer_name_ptrs) = unsafe {
             // SAFE because the name strings are not dropped until after
             // the call to create device.
@@ -74,12 +324,28 @@ impl LogicalDevice {
             ffi::to_os_ptrs(physical_device_extensions)
         };
 
-        let mut features = *physical_device.features();
+        let mut features = *physical_device.requested_features();
+        if api_version < vk::API_VERSION_1_3 {
+            features.omit_vulkan_13_features();
+        }
         let physical_device_features_v2 = unsafe {
             // SAFE because the features struct is not moved and is not
             // dropped until after the call to create device.
             features.link_p_next_chain()
         };
+        let (features_p_next, p_enabled_features) = if supports_features2 {
+            (
+                physical_device_features_v2 as *mut vk::PhysicalDeviceFeatures2
+                    as *const std::ffi::c_void,
+                std::ptr::null(),
+            )
+        } else {
+            (
+                std::ptr::null(),
+                &physical_device_features_v2.features
+                    as *const vk::PhysicalDeviceFeatures,
+            )
+        };
 
         let queue_create_infos: Vec<vk::DeviceQueueCreateInfo> =
             queue_family_infos
@@ -92,10 +358,21 @@ impl LogicalDevice {
                 })
                 .collect();
 
+        let device_group_create_info = vk::DeviceGroupDeviceCreateInfo {
+            p_next: features_p_next,
+            physical_device_count: device_group_handles.len() as u32,
+            p_physical_devices: device_group_handles.as_ptr(),
+            ..Default::default()
+        };
+        let p_next = if device_group_handles.is_empty() {
+            features_p_next
+        } else {
+            &device_group_create_info as *const vk::DeviceGroupDeviceCreateInfo
+                as *const std::ffi::c_void
+        };
+
         let create_info = vk::DeviceCreateInfo {
-            p_next: physical_device_features_v2
-                as *mut vk::PhysicalDeviceFeatures2
-                as *mut std::ffi::c_void,
+            p_next,
             queue_create_info_count: queue_create_infos.len() as u32,
             p_queue_create_infos: queue_create_infos.as_ptr(),
             enabled_layer_count: layer_name_ptrs.len() as u32,
@@ -103,9 +380,9 @@ impl LogicalDevice {
             enabled_extension_count: ext_name_ptrs.len() as u32,
             pp_enabled_extension_names: ext_name_ptrs.as_ptr(),
 
-            // Enabled Features is null because PhysicalDeviceFeatures2 is
+            // Enabled Features is null when PhysicalDeviceFeatures2 is
             // provided through the p_next pointer.
-            p_enabled_features: std::ptr::null(),
+            p_enabled_features,
 
             ..Default::default()
         };
@@ -120,13 +397,177 @@ impl LogicalDevice {
             )?
         };
 
+        let queues = queue_create_infos
+            .iter()
+            .map(|create_info| {
+                let family_queues = (0..create_info.queue_count)
+                    .map(|queue_index| unsafe {
+                        // SAFE because the queue was created along with the
+                        // device.
+                        Self::get_queue(&device, create_info, queue_index)
+                    })
+                    .collect();
+                (create_info.queue_family_index, family_queues)
+            })
+            .collect();
+
         Ok(Self {
             physical_device,
             active_physical_device_extensions: physical_device_extensions
                 .to_vec(),
+            active_layers: instance.layers().to_vec(),
+            enabled_features: features,
+            queues,
+            frames_in_flight: Self::DEFAULT_FRAMES_IN_FLIGHT,
             device,
         })
     }
+
+    /// Get a queue which was created along with the device.
+    ///
+    /// Queues created with flags, like protected queues, can only be
+    /// retrieved with vkGetDeviceQueue2.
+    unsafe fn get_queue(
+        device: &ash::Device,
+        create_info: &vk::DeviceQueueCreateInfo,
+        queue_index: u32,
+    ) -> vk::Queue {
+        if create_info.flags.is_empty() {
+            device.get_device_queue(create_info.queue_family_index, queue_index)
+        } else {
+            device.get_device_queue2(&vk::DeviceQueueInfo2 {
+                flags: create_info.flags,
+                queue_family_index: create_info.queue_family_index,
+                queue_index,
+                ..Default::default()
+            })
+        }
+    }
+
+    /// Add the device extensions needed by the requested features.
+    ///
+    /// Extensions which the device doesn't support are not added, a warning
+    /// is logged instead.
+    fn with_feature_extensions(
+        physical_device: &PhysicalDevice,
+        physical_device_extensions: &[String],
+    ) -> Vec<String> {
+        let mut extensions = physical_device_extensions.to_vec();
+        for name in physical_device.requested_features().required_extensions() {
+            let name = name.to_string_lossy().to_string();
+            if extensions.contains(&name) {
+                continue;
+            }
+            if physical_device.available_extension_names().contains(&name) {
+                log_debug!("Enabling {} for the requested features", name);
+                extensions.push(name);
+            } else {
+                log_warn!(
+                    "The requested features need {}, which {} doesn't support",
+                    name,
+                    physical_device.name()
+                );
+            }
+        }
+        extensions
+    }
+
+    /// Check that all requested extensions are available on the device.
+    ///
+    /// # Returns
+    ///
+    /// Returns an error listing every requested extension which the device
+    /// does not support.
+    fn check_extensions(
+        physical_device: &PhysicalDevice,
+        physical_device_extensions: &[String],
+    ) -> InstanceResult<()> {
+        let missing: Vec<String> = physical_device_extensions
+            .iter()
+            .filter(|name| {
+                !physical_device.available_extension_names().contains(name)
+            })
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            Err(InstanceError::MissingDeviceExtensions(missing))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check that every queue create flag has the features it requires.
+    ///
+    /// # Returns
+    ///
+    /// Returns an error if any family requests protected queues without
+    /// requesting the protected_memory feature.
+    fn check_queue_flags(
+        physical_device: &PhysicalDevice,
+        queue_family_infos: &[QueueFamilyInfo],
+    ) -> InstanceResult<()> {
+        let wants_protected_queues = queue_family_infos.iter().any(|info| {
+            info.flags().contains(vk::DeviceQueueCreateFlags::PROTECTED)
+        });
+        let protected_memory_enabled = physical_device
+            .requested_features()
+            .protected_memory_features()
+            .map(|features| features.protected_memory == vk::TRUE)
+            .unwrap_or(false);
+        if wants_protected_queues && !protected_memory_enabled {
+            Err(InstanceError::ProtectedMemoryNotEnabled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check that no queue family info needs more queues than its family
+    /// supports.
+    ///
+    /// Infos are merged before this check, so two infos which are valid on
+    /// their own can still exceed the family's queue count together.
+    fn check_queue_counts(
+        physical_device: &PhysicalDevice,
+        queue_family_infos: &[QueueFamilyInfo],
+    ) -> InstanceResult<()> {
+        let families = physical_device.queue_family_properties();
+        for info in queue_family_infos {
+            let queue_count = families
+                .get(info.queue_family_index() as usize)
+                .map(|properties| properties.queue_count)
+                .unwrap_or(0);
+            if info.queue_count() > queue_count {
+                return Err(InstanceError::TooManyQueues(
+                    info.queue_family_index(),
+                    queue_count,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that only core Vulkan 1.0 features are requested.
+    ///
+    /// Devices which can't chain PhysicalDeviceFeatures2 only accept the core
+    /// features, so any other requested feature would be silently ignored.
+    fn check_core_features_only(
+        physical_device: &PhysicalDevice,
+    ) -> InstanceResult<()> {
+        let requested = physical_device.requested_features();
+        let mut core_features = PhysicalDeviceFeatures::default();
+        *core_features.features_mut() = *requested.features();
+
+        let missing: Vec<String> = requested
+            .missing_features(&core_features)
+            .into_iter()
+            .map(str::to_owned)
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(InstanceError::MissingDeviceFeatures(missing))
+        }
+    }
 }
 
 impl Drop for LogicalDevice {
@@ -152,6 +593,9 @@ impl std::fmt::Debug for LogicalDevice {
                 "active_physical_device_extensions",
                 &self.active_physical_device_extensions,
             )
+            .field("active_layers", &self.active_layers)
+            .field("enabled_features", &self.enabled_features)
+            .field("frames_in_flight", &self.frames_in_flight)
             .field("device", &unsafe { self.raw().handle() })
             .finish()
     }
@@ -177,7 +621,13 @@ impl std::fmt::Display for LogicalDevice {
 impl VulkanHandle for LogicalDevice {
     type Handle = ash::Device;
 
+    const OBJECT_TYPE: vk::ObjectType = vk::ObjectType::DEVICE;
+
     unsafe fn raw(&self) -> &Self::Handle {
         &self.device
     }
+
+    unsafe fn raw_object_handle(&self) -> u64 {
+        self.device.handle().as_raw()
+    }
 }
@@ -0,0 +1,227 @@
+use {
+    crate::{
+        DeviceRequirements, InstanceError, InstanceResult, LogicalDevice,
+        PhysicalDevice, PhysicalDeviceFeatures, QueueFamilyInfo,
+        VulkanInstance,
+    },
+    ash::{
+        extensions::khr::{Surface, Swapchain},
+        vk,
+    },
+};
+
+impl LogicalDevice {
+    /// Create a logical device with a single compute queue.
+    ///
+    /// The first device which supports the requested features and has a
+    /// compute-capable queue family is used. One queue is created in that
+    /// family with priority 1.0.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical devices.
+    /// * `features` - the features the device must support.
+    ///
+    /// # Returns
+    ///
+    /// The logical device and its compute queue, or
+    /// [InstanceError::NoComputeDevice] if no compatible device exists.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the logical device must be dropped before the instance
+    /// used to create it.
+    pub unsafe fn new_compute(
+        instance: &VulkanInstance,
+        features: &PhysicalDeviceFeatures,
+    ) -> InstanceResult<(Self, vk::Queue)> {
+        let (physical_device, compute_queue_index) =
+            PhysicalDevice::enumerate_supported_devices(instance, features)?
+                .into_iter()
+                .find_map(|device| {
+                    let (index, _) = device
+                        .queue_families_with_flags(vk::QueueFlags::COMPUTE)
+                        .next()?;
+                    Some((device, index))
+                })
+                .ok_or(InstanceError::NoComputeDevice)?;
+
+        let mut family_info = QueueFamilyInfo::new(compute_queue_index);
+        family_info.add_queue_priority(1.0);
+
+        let logical_device =
+            Self::new(instance, physical_device, &[], &[family_info])?;
+        let compute_queue = unsafe {
+            // SAFE because exactly one queue was created in this family.
+            logical_device
+                .device
+                .get_device_queue(compute_queue_index, 0)
+        };
+
+        Ok((logical_device, compute_queue))
+    }
+
+    /// Create a logical device with a compute queue and a separate transfer
+    /// queue, e.g. for asynchronous uploads in a GPGPU pipeline.
+    ///
+    /// Devices which support the requested features and have a transfer
+    /// family distinct from their compute family are preferred. The transfer
+    /// family is picked with [PhysicalDevice::find_dedicated_transfer_family].
+    /// When the device only has one suitable family, two queues are created
+    /// in it if the family allows it. Otherwise a single queue is returned
+    /// for both.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical devices.
+    /// * `features` - the features the device must support.
+    ///
+    /// # Returns
+    ///
+    /// The logical device, its compute queue, and its transfer queue, or
+    /// [InstanceError::NoComputeDevice] if no compatible device exists.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the logical device must be dropped before the instance
+    /// used to create it.
+    pub unsafe fn new_compute_transfer(
+        instance: &VulkanInstance,
+        features: &PhysicalDeviceFeatures,
+    ) -> InstanceResult<(Self, vk::Queue, vk::Queue)> {
+        let candidates: Vec<(PhysicalDevice, u32, u32)> =
+            PhysicalDevice::enumerate_supported_devices(instance, features)?
+                .into_iter()
+                .filter_map(|device| {
+                    let (compute_family, _) = device
+                        .queue_families_with_flags(vk::QueueFlags::COMPUTE)
+                        .next()?;
+                    // Compute families always support transfer operations,
+                    // even when they don't report the TRANSFER flag.
+                    let transfer_family = device
+                        .find_dedicated_transfer_family()
+                        .unwrap_or(compute_family);
+                    Some((device, compute_family, transfer_family))
+                })
+                .collect();
+        let separate_families = candidates
+            .iter()
+            .position(|(_, compute_family, transfer_family)| {
+                compute_family != transfer_family
+            })
+            .unwrap_or(0);
+        let (physical_device, compute_family, transfer_family) = candidates
+            .into_iter()
+            .nth(separate_families)
+            .ok_or(InstanceError::NoComputeDevice)?;
+
+        let family_infos = if compute_family != transfer_family {
+            vec![
+                QueueFamilyInfo::from_priorities(compute_family, [1.0])?,
+                QueueFamilyInfo::from_priorities(transfer_family, [1.0])?,
+            ]
+        } else {
+            let queue_count = physical_device.queue_family_properties()
+                [compute_family as usize]
+                .queue_count
+                .min(2);
+            vec![QueueFamilyInfo::from_priorities(
+                compute_family,
+                [1.0, 1.0].into_iter().take(queue_count as usize),
+            )?]
+        };
+
+        let logical_device =
+            Self::new(instance, physical_device, &[], &family_infos)?;
+        let compute_queue =
+            logical_device.queue_handles_for_family(compute_family)[0];
+        // When the families match, the transfer queue is the second queue in
+        // the family, or the compute queue if only one could be created.
+        let transfer_queue = *logical_device
+            .queue_handles_for_family(transfer_family)
+            .last()
+            .unwrap();
+
+        Ok((logical_device, compute_queue, transfer_queue))
+    }
+
+    /// Create a logical device which can render and present to a surface.
+    ///
+    /// The first device which satisfies the requirements and can present to
+    /// the surface is used. One queue is created in the graphics family and,
+    /// if it differs, one in the present family. VK_KHR_swapchain is always
+    /// enabled along with any extensions in the requirements.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical devices.
+    /// * `surface_loader` - the VK_KHR_surface extension loader.
+    /// * `surface` - the surface which will be presented to.
+    /// * `requirements` - the device requirements, typically
+    ///   [DeviceRequirements::for_graphics].
+    ///
+    /// # Returns
+    ///
+    /// The logical device, its graphics queue, and its present queue. The
+    /// two queues are the same when one family supports both. Returns
+    /// [InstanceError::NoGraphicsDevice] if no compatible device exists.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because:
+    ///   - the surface must be a valid handle created with the instance.
+    ///   - the logical device must be dropped before the instance used to
+    ///     create it.
+    pub unsafe fn new_graphics(
+        instance: &VulkanInstance,
+        surface_loader: &Surface,
+        surface: vk::SurfaceKHR,
+        requirements: &DeviceRequirements,
+    ) -> InstanceResult<(Self, vk::Queue, vk::Queue)> {
+        let mut selected = None;
+        for device in requirements.enumerate(instance)? {
+            if let Some((graphics_family, present_family, same_family)) = device
+                .find_graphics_and_present_families(surface_loader, surface)?
+            {
+                selected = Some((
+                    device,
+                    graphics_family,
+                    present_family,
+                    same_family,
+                ));
+                break;
+            }
+        }
+        let (physical_device, graphics_family, present_family, same_family) =
+            selected.ok_or(InstanceError::NoGraphicsDevice)?;
+
+        let mut family_infos = vec![QueueFamilyInfo::new(graphics_family)];
+        if !same_family {
+            family_infos.push(QueueFamilyInfo::new(present_family));
+        }
+        for family_info in &mut family_infos {
+            family_info.add_queue_priority(1.0);
+        }
+
+        let swapchain = Swapchain::name().to_str().unwrap().to_owned();
+        let mut extensions = requirements.required_extensions().to_vec();
+        if !extensions.contains(&swapchain) {
+            extensions.push(swapchain);
+        }
+
+        let logical_device =
+            Self::new(instance, physical_device, &extensions, &family_infos)?;
+        let (graphics_queue, present_queue) = unsafe {
+            // SAFE because exactly one queue was created in each family.
+            (
+                logical_device.device.get_device_queue(graphics_family, 0),
+                logical_device.device.get_device_queue(present_family, 0),
+            )
+        };
+
+        Ok((logical_device, graphics_queue, present_queue))
+    }
+}
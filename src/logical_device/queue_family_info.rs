@@ -1,4 +1,7 @@
-use ash::vk;
+use {
+    crate::{InstanceError, InstanceResult, PhysicalDevice},
+    ash::vk,
+};
 
 /// The logical device constructor uses an array of these structs to build the
 /// Vulkan DeviceQueueCreateInfo structs.
@@ -25,8 +28,19 @@ use ash::vk;
 pub struct QueueFamilyInfo {
     queue_family_index: u32,
     queue_priorities: Vec<f32>,
+    max_queue_count: Option<u32>,
+    flags: vk::DeviceQueueCreateFlags,
+    global_priority: Option<Box<vk::DeviceQueueGlobalPriorityCreateInfoKHR>>,
 }
 
+/// # Safety
+///
+/// Send and Sync must be implemented explicitly because the global priority
+/// create info has a p_next pointer. That pointer is always null and is never
+/// written by this struct.
+unsafe impl Send for QueueFamilyInfo {}
+unsafe impl Sync for QueueFamilyInfo {}
+
 impl QueueFamilyInfo {
     /// Create a new instance with no queue priorities.
     ///
@@ -38,9 +52,148 @@ impl QueueFamilyInfo {
         Self {
             queue_family_index,
             queue_priorities: vec![],
+            max_queue_count: None,
+            flags: vk::DeviceQueueCreateFlags::empty(),
+            global_priority: None,
         }
     }
 
+    /// Create a new instance with one queue for each priority.
+    ///
+    /// # Params
+    ///
+    /// * `queue_family_index` - index for the underlying queue family.
+    /// * `priorities` - the priority for each queue, see
+    ///   [QueueFamilyInfo::add_queue_priority].
+    ///
+    /// # Returns
+    ///
+    /// Returns an error if any priority is outside the range 0.0 to 1.0.
+    pub fn from_priorities(
+        queue_family_index: u32,
+        priorities: impl IntoIterator<Item = f32>,
+    ) -> InstanceResult<Self> {
+        let mut queue_family_info = Self::new(queue_family_index);
+        for priority in priorities {
+            if !(0.0..=1.0).contains(&priority) {
+                return Err(InstanceError::InvalidQueuePriority(
+                    queue_family_index,
+                    priority,
+                ));
+            }
+            queue_family_info.add_queue_priority(priority);
+        }
+        Ok(queue_family_info)
+    }
+
+    /// Create a new instance which knows how many queues the family supports.
+    ///
+    /// Use [QueueFamilyInfo::try_add_queue_priority] to add queues without
+    /// exceeding the family's limit.
+    ///
+    /// # Params
+    ///
+    /// * `physical_device` - the physical device which owns the queue family.
+    /// * `queue_family_index` - index for the underlying queue family. Indices
+    ///   which are out of range support zero queues.
+    pub fn with_max_queues(
+        physical_device: &PhysicalDevice,
+        queue_family_index: u32,
+    ) -> Self {
+        let max_queue_count = physical_device
+            .queue_family_properties()
+            .get(queue_family_index as usize)
+            .map(|properties| properties.queue_count)
+            .unwrap_or(0);
+        Self {
+            max_queue_count: Some(max_queue_count),
+            ..Self::new(queue_family_index)
+        }
+    }
+
+    /// Combine infos which refer to the same queue family and use the same
+    /// flags.
+    ///
+    /// Vulkan requires each queue family to appear at most once per set of
+    /// queue create flags when creating a logical device, e.g. a family can
+    /// have one info for protected queues and one for unprotected queues.
+    /// Infos with the same index and flags are merged into the first one:
+    /// their priorities are appended in order. The first info's global
+    /// priority and queue limit are kept.
+    ///
+    /// The merged queue counts are not checked here. [LogicalDevice::new]
+    /// returns [InstanceError::TooManyQueues] if a merged info needs more
+    /// queues than its family supports.
+    ///
+    /// # Params
+    ///
+    /// * `queue_family_infos` - the infos to merge.
+    ///
+    /// # Returns
+    ///
+    /// One info per unique queue family index and flags, in the order each
+    /// pair first appeared. Queues from a merged info follow the queues from
+    /// the infos before it, so the second info's first queue has the index
+    /// `first_info_queue_count` within the family.
+    ///
+    /// [LogicalDevice::new]: crate::LogicalDevice::new
+    pub fn merge_by_index(
+        queue_family_infos: &[QueueFamilyInfo],
+    ) -> Vec<QueueFamilyInfo> {
+        let mut merged: Vec<QueueFamilyInfo> = vec![];
+        for info in queue_family_infos {
+            match merged.iter_mut().find(|existing| {
+                existing.queue_family_index == info.queue_family_index
+                    && existing.flags == info.flags
+            }) {
+                Some(existing) => {
+                    existing
+                        .queue_priorities
+                        .extend_from_slice(&info.queue_priorities);
+                }
+                None => merged.push(info.clone()),
+            }
+        }
+        merged
+    }
+
+    /// The index of the queue family which this info describes.
+    pub fn queue_family_index(&self) -> u32 {
+        self.queue_family_index
+    }
+
+    /// The number of queues which will be created in the family.
+    pub fn queue_count(&self) -> u32 {
+        self.queue_priorities.len() as u32
+    }
+
+    /// Add a queue with the given priority if the family has room for it.
+    ///
+    /// # Params
+    ///
+    /// * `priority` - see [QueueFamilyInfo::add_queue_priority].
+    ///
+    /// # Returns
+    ///
+    /// Returns an error if adding the queue would exceed the family's queue
+    /// count. Instances created with [QueueFamilyInfo::new] have no known
+    /// limit, so this never fails for them.
+    pub fn try_add_queue_priority(
+        &mut self,
+        priority: f32,
+    ) -> InstanceResult<()> {
+        if let Some(max_queue_count) = self.max_queue_count {
+            if self.queue_priorities.len() as u32 >= max_queue_count {
+                return Err(InstanceError::TooManyQueues(
+                    self.queue_family_index,
+                    max_queue_count,
+                ));
+            }
+        }
+        self.add_queue_priority(priority);
+        Ok(())
+    }
+
     /// Add a queue with the given priority.
     ///
     /// # Params
@@ -53,21 +206,102 @@ impl QueueFamilyInfo {
         self.queue_priorities.push(priority);
     }
 
+    /// The flags used when creating queues in this family.
+    pub fn flags(&self) -> vk::DeviceQueueCreateFlags {
+        self.flags
+    }
+
+    /// Replace the flags used when creating queues in this family.
+    ///
+    /// Flags which require a device feature, like PROTECTED, are validated
+    /// when the logical device is created.
+    ///
+    /// # Params
+    ///
+    /// * `flags` - the queue create flags applied by
+    ///   [QueueFamilyInfo::as_queue_create_info].
+    pub fn set_flags(&mut self, flags: vk::DeviceQueueCreateFlags) {
+        self.flags = flags;
+    }
+
+    /// Create protected-capable queues in this family.
+    ///
+    /// Protected queues require the protected_memory feature to be enabled
+    /// on the logical device.
+    ///
+    /// # Params
+    ///
+    /// * `protected` - true to set the PROTECTED queue create flag, false to
+    ///   clear it.
+    pub fn set_protected(&mut self, protected: bool) {
+        self.flags
+            .set(vk::DeviceQueueCreateFlags::PROTECTED, protected);
+    }
+
+    /// Set the system-wide priority for every queue created in this family.
+    ///
+    /// The global priority struct is chained into the generated
+    /// DeviceQueueCreateInfo. The caller is responsible for enabling the
+    /// VK_KHR_global_priority device extension. Use
+    /// [PhysicalDevice::queue_family_supports_global_priority] to check
+    /// whether the family supports the priority first.
+    ///
+    /// # Params
+    ///
+    /// * `priority` - the global priority for the family's queues.
+    pub fn set_global_priority(
+        &mut self,
+        priority: vk::QueueGlobalPriorityKHR,
+    ) {
+        match &mut self.global_priority {
+            Some(create_info) => create_info.global_priority = priority,
+            None => {
+                self.global_priority =
+                    Some(Box::new(vk::DeviceQueueGlobalPriorityCreateInfoKHR {
+                        global_priority: priority,
+                        ..Default::default()
+                    }))
+            }
+        }
+    }
+
     /// Get a DeviceQueueCreateInfo struct based on the number of queue
     /// priorities specified for this queue family.
     ///
     /// # Safety
     ///
     /// Unsafe because the device queue create info struct contains a pointer
-    /// to the queue_priorities vector. This means that if any queue priorities
-    /// are added AFTER calling this function, then using the struct will cause
-    /// undefined behavior.
+    /// to the queue_priorities vector and the global priority struct. This
+    /// means that if any queue priorities are added AFTER calling this
+    /// function, or if this struct is dropped, then using the struct will
+    /// cause undefined behavior.
     pub unsafe fn as_queue_create_info(&self) -> vk::DeviceQueueCreateInfo {
+        let p_next = match &self.global_priority {
+            Some(create_info) => create_info.as_ref()
+                as *const vk::DeviceQueueGlobalPriorityCreateInfoKHR
+                as *const std::ffi::c_void,
+            None => std::ptr::null(),
+        };
         vk::DeviceQueueCreateInfo {
+            p_next,
+            flags: self.flags,
             queue_family_index: self.queue_family_index,
-            queue_count: self.queue_priorities.len() as u32,
+            queue_count: self.queue_count(),
             p_queue_priorities: self.queue_priorities.as_ptr(),
             ..Default::default()
         }
     }
 }
+
+impl std::fmt::Display for QueueFamilyInfo {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let queue_count = self.queue_priorities.len();
+        formatter.write_fmt(format_args!(
+            "family {}: {} {} {:?}",
+            self.queue_family_index,
+            queue_count,
+            if queue_count == 1 { "queue" } else { "queues" },
+            self.queue_priorities,
+        ))
+    }
+}
@@ -0,0 +1,54 @@
+use {
+    crate::{
+        ffi, InstanceError, InstanceResult, PhysicalDevice, VulkanInstance,
+    },
+    ash::vk,
+};
+
+impl PhysicalDevice {
+    /// Query the matrix sizes and component types supported by cooperative
+    /// matrix operations in shaders.
+    ///
+    /// This uses the VK_NV_cooperative_matrix extension because
+    /// VK_KHR_cooperative_matrix is newer than the Vulkan headers supported by
+    /// this version of Ash. The device extension must also be enabled on the
+    /// logical device, along with the `cooperative_matrix` feature, before
+    /// shaders can use the matrices.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical device.
+    ///
+    /// # Returns
+    ///
+    /// The supported matrix configurations, or MissingDeviceExtensions when
+    /// the device doesn't support the VK_NV_cooperative_matrix extension.
+    pub fn cooperative_matrix_properties(
+        &self,
+        instance: &VulkanInstance,
+    ) -> InstanceResult<Vec<vk::CooperativeMatrixPropertiesNV>> {
+        let extension_name = vk::NvCooperativeMatrixFn::name();
+        if self
+            .extension_version(&extension_name.to_string_lossy())
+            .is_none()
+        {
+            return Err(InstanceError::MissingDeviceExtensions(vec![
+                extension_name.to_string_lossy().to_string(),
+            ]));
+        }
+
+        let fp =
+            instance.load_instance_fns(|f| vk::NvCooperativeMatrixFn::load(f));
+        let properties = unsafe {
+            ffi::enumerate(|count, values| {
+                (fp.get_physical_device_cooperative_matrix_properties_nv)(
+                    self.physical_device,
+                    count,
+                    values,
+                )
+            })?
+        };
+        Ok(properties)
+    }
+}
@@ -0,0 +1,239 @@
+use {
+    crate::{
+        InstanceResult, PhysicalDevice, PhysicalDeviceFeatures, VulkanInstance,
+    },
+    ash::{extensions::khr::Swapchain, vk},
+};
+
+/// A declarative set of requirements used to select physical devices.
+///
+/// # Examples
+///
+/// ```
+/// use {
+///     ash::vk,
+///     ccthw_ash_instance::{DeviceRequirements, VulkanInstance},
+/// };
+///
+/// let instance = unsafe { VulkanInstance::new(&[], &[]).unwrap() };
+/// let devices = DeviceRequirements::new()
+///     .queue_flags(vk::QueueFlags::COMPUTE)
+///     .min_device_local_memory(256 * 1024 * 1024)
+///     .enumerate(&instance)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DeviceRequirements {
+    features: PhysicalDeviceFeatures,
+    extensions: Vec<String>,
+    extension_spec_versions: Vec<(String, u32)>,
+    queue_flags: vk::QueueFlags,
+    min_device_local_memory: u64,
+    min_api_version: u32,
+}
+
+impl DeviceRequirements {
+    /// Create requirements which are satisfied by every device.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The minimal requirements for rendering to a swapchain: a graphics
+    /// queue, the VK_KHR_swapchain extension, and sampler anisotropy.
+    ///
+    /// Presentation support depends on the surface, so it's checked by
+    /// [LogicalDevice::new_graphics] rather than by these requirements.
+    ///
+    /// [LogicalDevice::new_graphics]: crate::LogicalDevice::new_graphics
+    pub fn for_graphics() -> Self {
+        let mut features = PhysicalDeviceFeatures::default();
+        features.features_mut().sampler_anisotropy = vk::TRUE;
+        Self::new()
+            .features(features)
+            .extension(Swapchain::name().to_str().unwrap())
+            .queue_flags(vk::QueueFlags::GRAPHICS)
+    }
+
+    /// Require every feature which is enabled in `features`.
+    ///
+    /// Features accumulate, so features required by earlier calls, or by a
+    /// preset like [DeviceRequirements::for_graphics], are kept.
+    pub fn features(mut self, features: PhysicalDeviceFeatures) -> Self {
+        self.features.merge(&features);
+        self
+    }
+
+    /// Require a device extension.
+    pub fn extension(mut self, name: impl Into<String>) -> Self {
+        self.extensions.push(name.into());
+        self
+    }
+
+    /// Require a device extension with at least the given spec version.
+    ///
+    /// This is useful for extensions whose behavior changed between
+    /// revisions. Devices with an older revision are rejected.
+    pub fn extension_with_spec_version(
+        mut self,
+        name: impl Into<String>,
+        min_spec_version: u32,
+    ) -> Self {
+        let name = name.into();
+        self.extension_spec_versions
+            .push((name.clone(), min_spec_version));
+        self.extension(name)
+    }
+
+    /// Require every device extension in `names`.
+    pub fn extensions(mut self, names: &[String]) -> Self {
+        self.extensions.extend_from_slice(names);
+        self
+    }
+
+    /// Require at least one queue family which supports all of `flags`.
+    pub fn queue_flags(mut self, flags: vk::QueueFlags) -> Self {
+        self.queue_flags |= flags;
+        self
+    }
+
+    /// Require at least `bytes` of device-local memory.
+    pub fn min_device_local_memory(mut self, bytes: u64) -> Self {
+        self.min_device_local_memory = bytes;
+        self
+    }
+
+    /// Require a device which reports at least the given Vulkan version,
+    /// e.g. `vk::API_VERSION_1_2`.
+    ///
+    /// Devices can advertise newer features through extensions without
+    /// supporting the core semantics of the version which promoted them.
+    pub fn min_api_version(mut self, api_version: u32) -> Self {
+        self.min_api_version = api_version;
+        self
+    }
+
+    /// The features required by these requirements.
+    pub fn required_features(&self) -> &PhysicalDeviceFeatures {
+        &self.features
+    }
+
+    /// The queue flags which at least one queue family must support.
+    pub fn required_queue_flags(&self) -> vk::QueueFlags {
+        self.queue_flags
+    }
+
+    /// The device extensions required by these requirements.
+    pub fn required_extensions(&self) -> &[String] {
+        &self.extensions
+    }
+
+    /// Returns true when the device satisfies every requirement.
+    pub fn is_satisfied_by(&self, device: &PhysicalDevice) -> bool {
+        self.unmet_requirements(device).is_empty()
+    }
+
+    /// Describe every requirement which the device doesn't satisfy.
+    ///
+    /// # Returns
+    ///
+    /// A human-readable description of each unmet requirement. The list is
+    /// empty when the device satisfies every requirement.
+    pub fn unmet_requirements(&self, device: &PhysicalDevice) -> Vec<String> {
+        let mut unmet = vec![];
+        if !self.features.is_supported_by(device.features()) {
+            unmet.push("missing required features".to_owned());
+        }
+        let missing_extensions: Vec<&String> = self
+            .extensions
+            .iter()
+            .filter(|name| !device.available_extension_names().contains(name))
+            .collect();
+        if !missing_extensions.is_empty() {
+            unmet.push(format!("missing extensions {:?}", missing_extensions));
+        }
+        for (name, min_spec_version) in &self.extension_spec_versions {
+            match device.extension_version(name) {
+                Some(spec_version) if spec_version < *min_spec_version => {
+                    unmet.push(format!(
+                        "{} spec version {} is older than {}",
+                        name, spec_version, min_spec_version
+                    ));
+                }
+                _ => (),
+            }
+        }
+        if device
+            .queue_families_with_flags(self.queue_flags)
+            .next()
+            .is_none()
+        {
+            unmet.push(format!(
+                "no queue family supports {:?}",
+                self.queue_flags
+            ));
+        }
+        if !device
+            .has_device_local_memory_at_least(self.min_device_local_memory)
+        {
+            unmet.push(format!(
+                "less than {} bytes of device-local memory",
+                self.min_device_local_memory
+            ));
+        }
+        let api_version = device.properties().properties().api_version;
+        if api_version < self.min_api_version {
+            unmet.push(format!(
+                "Vulkan {}.{} is older than the required Vulkan {}.{}",
+                vk::api_version_major(api_version),
+                vk::api_version_minor(api_version),
+                vk::api_version_major(self.min_api_version),
+                vk::api_version_minor(self.min_api_version),
+            ));
+        }
+        unmet
+    }
+
+    /// Keep only the devices which satisfy every requirement.
+    ///
+    /// # Params
+    ///
+    /// * `devices` - the candidate devices, in order of preference.
+    ///
+    /// # Returns
+    ///
+    /// The devices which satisfy the requirements, in their original order.
+    pub fn filter(
+        &self,
+        devices: impl IntoIterator<Item = PhysicalDevice>,
+    ) -> Vec<PhysicalDevice> {
+        devices
+            .into_iter()
+            .filter(|device| {
+                let unmet = self.unmet_requirements(device);
+                if !unmet.is_empty() {
+                    log_debug!("Rejected {}: {:?}", device.name(), unmet);
+                }
+                unmet.is_empty()
+            })
+            .collect()
+    }
+
+    /// Enumerate every physical device which satisfies the requirements.
+    ///
+    /// The required features are requested on each returned device, so they
+    /// are enabled when creating a logical device.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which will provide access to the
+    ///   physical devices.
+    pub fn enumerate(
+        &self,
+        instance: &VulkanInstance,
+    ) -> InstanceResult<Vec<PhysicalDevice>> {
+        Ok(self.filter(PhysicalDevice::enumerate_supported_devices(
+            instance,
+            &self.features,
+        )?))
+    }
+}
@@ -0,0 +1,74 @@
+use {
+    crate::{PhysicalDevice, VulkanInstance},
+    ash::vk,
+};
+
+impl PhysicalDevice {
+    /// Query the external handle types supported for buffers with the given
+    /// usage.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical device.
+    /// * `external_buffer_info` - the buffer usage and external handle type to
+    ///   query.
+    ///
+    /// # Returns
+    ///
+    /// None when the device or instance predates Vulkan 1.1, which added the
+    /// query.
+    pub fn external_buffer_properties(
+        &self,
+        instance: &VulkanInstance,
+        external_buffer_info: &vk::PhysicalDeviceExternalBufferInfo,
+    ) -> Option<vk::ExternalBufferProperties> {
+        if self.usable_api_version(instance) < vk::API_VERSION_1_1 {
+            return None;
+        }
+        let mut properties = vk::ExternalBufferProperties::default();
+        unsafe {
+            instance
+                .ash()
+                .get_physical_device_external_buffer_properties(
+                    self.physical_device,
+                    external_buffer_info,
+                    &mut properties,
+                );
+        }
+        Some(properties)
+    }
+
+    /// Query the external handle types supported for semaphores.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical device.
+    /// * `external_semaphore_info` - the external handle type to query.
+    ///
+    /// # Returns
+    ///
+    /// None when the device or instance predates Vulkan 1.1, which added the
+    /// query.
+    pub fn external_semaphore_properties(
+        &self,
+        instance: &VulkanInstance,
+        external_semaphore_info: &vk::PhysicalDeviceExternalSemaphoreInfo,
+    ) -> Option<vk::ExternalSemaphoreProperties> {
+        if self.usable_api_version(instance) < vk::API_VERSION_1_1 {
+            return None;
+        }
+        let mut properties = vk::ExternalSemaphoreProperties::default();
+        unsafe {
+            instance
+                .ash()
+                .get_physical_device_external_semaphore_properties(
+                    self.physical_device,
+                    external_semaphore_info,
+                    &mut properties,
+                );
+        }
+        Some(properties)
+    }
+}
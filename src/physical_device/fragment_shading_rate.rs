@@ -0,0 +1,48 @@
+use {
+    crate::{
+        ffi, InstanceError, InstanceResult, PhysicalDevice, VulkanInstance,
+    },
+    ash::vk,
+};
+
+impl PhysicalDevice {
+    /// Query the fragment sizes and sample counts supported for variable rate
+    /// shading.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical device.
+    ///
+    /// # Returns
+    ///
+    /// The supported fragment shading rates, or MissingDeviceExtensions when
+    /// the device doesn't support the VK_KHR_fragment_shading_rate extension.
+    pub fn fragment_shading_rates(
+        &self,
+        instance: &VulkanInstance,
+    ) -> InstanceResult<Vec<vk::PhysicalDeviceFragmentShadingRateKHR>> {
+        let extension_name = vk::KhrFragmentShadingRateFn::name();
+        if self
+            .extension_version(&extension_name.to_string_lossy())
+            .is_none()
+        {
+            return Err(InstanceError::MissingDeviceExtensions(vec![
+                extension_name.to_string_lossy().to_string(),
+            ]));
+        }
+
+        let fp = instance
+            .load_instance_fns(|f| vk::KhrFragmentShadingRateFn::load(f));
+        let rates = unsafe {
+            ffi::enumerate(|count, values| {
+                (fp.get_physical_device_fragment_shading_rates_khr)(
+                    self.physical_device,
+                    count,
+                    values,
+                )
+            })?
+        };
+        Ok(rates)
+    }
+}
@@ -0,0 +1,65 @@
+use {crate::PhysicalDevice, ash::vk};
+
+impl PhysicalDevice {
+    /// Build a machine-readable capability report for this device.
+    ///
+    /// The report is a JSON object with the following keys:
+    ///
+    /// * `name` - the device name.
+    /// * `device_type` - the device type, e.g. "DISCRETE_GPU".
+    /// * `vendor_id` and `device_id` - the PCI-style identifiers.
+    /// * `api_version` - the supported Vulkan version, e.g. "1.3.250".
+    /// * `driver_version` - the raw, vendor-specific driver version.
+    /// * `limits` - a selection of commonly-needed device limits.
+    /// * `features` - the names of all supported features.
+    /// * `extensions` - the names of all available device extensions.
+    ///
+    /// Only available with the `json` feature.
+    pub fn to_json(&self) -> String {
+        let properties = self.properties().properties();
+        let limits = &properties.limits;
+        let api_version = format!(
+            "{}.{}.{}",
+            vk::api_version_major(properties.api_version),
+            vk::api_version_minor(properties.api_version),
+            vk::api_version_patch(properties.api_version),
+        );
+        serde_json::json!({
+            "name": self.name(),
+            "device_type": format!("{:?}", properties.device_type),
+            "vendor_id": properties.vendor_id,
+            "device_id": properties.device_id,
+            "api_version": api_version,
+            "driver_version": properties.driver_version,
+            "limits": {
+                "max_image_dimension2_d": limits.max_image_dimension2_d,
+                "max_bound_descriptor_sets": limits.max_bound_descriptor_sets,
+                "max_push_constants_size": limits.max_push_constants_size,
+                "max_memory_allocation_count":
+                    limits.max_memory_allocation_count,
+                "max_uniform_buffer_range": limits.max_uniform_buffer_range,
+                "max_storage_buffer_range": limits.max_storage_buffer_range,
+                "max_compute_shared_memory_size":
+                    limits.max_compute_shared_memory_size,
+                "max_compute_work_group_count":
+                    limits.max_compute_work_group_count,
+                "max_compute_work_group_invocations":
+                    limits.max_compute_work_group_invocations,
+                "max_compute_work_group_size":
+                    limits.max_compute_work_group_size,
+                "max_sampler_anisotropy": limits.max_sampler_anisotropy,
+                "min_uniform_buffer_offset_alignment":
+                    limits.min_uniform_buffer_offset_alignment,
+                "min_storage_buffer_offset_alignment":
+                    limits.min_storage_buffer_offset_alignment,
+                "non_coherent_atom_size": limits.non_coherent_atom_size,
+                "timestamp_period": limits.timestamp_period,
+                "max_usable_sample_count":
+                    self.max_usable_sample_count().as_raw(),
+            },
+            "features": self.features().enabled_feature_names(),
+            "extensions": self.available_extension_names(),
+        })
+        .to_string()
+    }
+}
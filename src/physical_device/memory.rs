@@ -0,0 +1,64 @@
+use {crate::PhysicalDevice, ash::vk};
+
+impl PhysicalDevice {
+    /// Returns true when the device's DEVICE_LOCAL memory heaps add up to at
+    /// least the requested number of bytes.
+    ///
+    /// Useful for filtering out underpowered devices during selection.
+    ///
+    /// # Params
+    ///
+    /// * `bytes` - the minimum total size of all DEVICE_LOCAL heaps.
+    pub fn has_device_local_memory_at_least(&self, bytes: u64) -> bool {
+        self.device_local_memory_bytes() >= bytes
+    }
+
+    /// The total size, in bytes, of every DEVICE_LOCAL memory heap.
+    ///
+    /// This is the number usually shown as the GPU's VRAM. On integrated
+    /// GPUs the device-local heap is often also host-visible, so it's
+    /// counted here and by [PhysicalDevice::host_visible_memory_bytes].
+    pub fn device_local_memory_bytes(&self) -> u64 {
+        self.memory_heaps()
+            .iter()
+            .filter(|heap| {
+                heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL)
+            })
+            .map(|heap| heap.size)
+            .sum()
+    }
+
+    /// The total size, in bytes, of every memory heap which has at least one
+    /// HOST_VISIBLE memory type.
+    ///
+    /// Each heap is counted once, no matter how many of its memory types are
+    /// host-visible. Heaps which are also DEVICE_LOCAL are counted here and
+    /// by [PhysicalDevice::device_local_memory_bytes], so the two totals can
+    /// add up to more than the device's memory.
+    pub fn host_visible_memory_bytes(&self) -> u64 {
+        let memory_properties = self.memory_properties();
+        let memory_types = &memory_properties.memory_types
+            [..memory_properties.memory_type_count as usize];
+        self.memory_heaps()
+            .iter()
+            .enumerate()
+            .filter(|(heap_index, _)| {
+                memory_types.iter().any(|memory_type| {
+                    memory_type.heap_index as usize == *heap_index
+                        && memory_type
+                            .property_flags
+                            .contains(vk::MemoryPropertyFlags::HOST_VISIBLE)
+                })
+            })
+            .map(|(_, heap)| heap.size)
+            .sum()
+    }
+
+    /// The device's memory heaps, without the unused tail of the fixed-size
+    /// array.
+    fn memory_heaps(&self) -> &[vk::MemoryHeap] {
+        let memory_properties = self.memory_properties();
+        &memory_properties.memory_heaps
+            [..memory_properties.memory_heap_count as usize]
+    }
+}
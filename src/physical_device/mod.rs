@@ -1,18 +1,37 @@
+mod cooperative_matrix;
+mod device_requirements;
+mod external_properties;
+mod fragment_shading_rate;
+#[cfg(feature = "json")]
+mod json;
+mod memory;
 mod physical_device_features;
+mod physical_device_group;
+mod physical_device_info;
 mod physical_device_properties;
+mod presentation_support;
+mod queue_families;
+mod reject_reason;
+mod surface;
+mod tool_properties;
 
 use {
-    crate::{ffi, InstanceResult, VulkanHandle, VulkanInstance},
-    ash::vk,
+    crate::{ffi, InstanceError, InstanceResult, VulkanHandle, VulkanInstance},
+    ash::vk::{self, Handle},
     indoc::indoc,
 };
 
 pub use self::{
-    physical_device_features::PhysicalDeviceFeatures,
+    device_requirements::DeviceRequirements,
+    physical_device_features::{FeatureDiff, PhysicalDeviceFeatures},
+    physical_device_group::PhysicalDeviceGroup,
+    physical_device_info::PhysicalDeviceInfo,
     physical_device_properties::PhysicalDeviceProperties,
+    reject_reason::RejectReason,
 };
 
-/// A Vulkan physical device along with its properties and requested features.
+/// A Vulkan physical device along with its properties, available features,
+/// and requested features.
 ///
 /// Physical devices are purely descriptive and can be cloned without concern
 /// for underlying GPU resources.
@@ -20,9 +39,13 @@ pub use self::{
 pub struct PhysicalDevice {
     properties: PhysicalDeviceProperties,
     features: PhysicalDeviceFeatures,
+    requested_features: PhysicalDeviceFeatures,
     available_extensions: Vec<vk::ExtensionProperties>,
     available_extension_names: Vec<String>,
     queue_family_properties: Vec<vk::QueueFamilyProperties>,
+    queue_family_global_priorities:
+        Vec<vk::QueueFamilyGlobalPriorityPropertiesKHR>,
+    memory_properties: vk::PhysicalDeviceMemoryProperties,
     physical_device: vk::PhysicalDevice,
 }
 
@@ -45,6 +68,11 @@ impl PhysicalDevice {
         &self.queue_family_properties
     }
 
+    /// The memory heaps and memory types supported by this device.
+    pub fn memory_properties(&self) -> &vk::PhysicalDeviceMemoryProperties {
+        &self.memory_properties
+    }
+
     /// The set of all extensions available on this device.
     pub fn available_extensions(&self) -> &[vk::ExtensionProperties] {
         &self.available_extensions
@@ -55,24 +83,217 @@ impl PhysicalDevice {
         &self.available_extension_names
     }
 
+    /// The spec version of an available extension.
+    ///
+    /// # Params
+    ///
+    /// * `name` - the extension name, e.g. "VK_KHR_swapchain".
+    ///
+    /// # Returns
+    ///
+    /// The extension's `spec_version`, or None if the extension is not
+    /// available on this device.
+    pub fn extension_version(&self, name: &str) -> Option<u32> {
+        self.available_extensions
+            .iter()
+            .find(|properties| {
+                ffi::string_from_i8(&properties.extension_name)
+                    .map(|available| available == name)
+                    .unwrap_or(false)
+            })
+            .map(|properties| properties.spec_version)
+    }
+
     /// The properties for this physical device.
     pub fn properties(&self) -> &PhysicalDeviceProperties {
         &self.properties
     }
 
-    /// The features requested when picking this device.
+    /// The newest Vulkan version which can be used with this device, i.e. the
+    /// older of the device's and the instance's API versions.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical device.
+    pub(crate) fn usable_api_version(&self, instance: &VulkanInstance) -> u32 {
+        self.properties()
+            .properties()
+            .api_version
+            .min(instance.api_version())
+    }
+
+    /// The features actually supported by this device.
     pub fn features(&self) -> &PhysicalDeviceFeatures {
         &self.features
     }
 
+    /// The features requested when picking this device. These are the
+    /// features enabled when creating a logical device.
+    pub fn requested_features(&self) -> &PhysicalDeviceFeatures {
+        &self.requested_features
+    }
+
+    /// Create a copy of this device which requests a different set of
+    /// features.
+    ///
+    /// This is useful for creating more than one logical device from the same
+    /// enumerated device without enumerating again.
+    ///
+    /// # Params
+    ///
+    /// * `features` - the features to enable when creating a logical device.
+    ///   These are not checked against the device's supported features, use
+    ///   [PhysicalDeviceFeatures::is_supported_by] to verify them first.
+    pub fn with_features(&self, features: PhysicalDeviceFeatures) -> Self {
+        Self {
+            requested_features: features,
+            ..self.clone()
+        }
+    }
+
+    /// Get a copy of every feature supported by this device.
+    ///
+    /// This is the lazy-but-safe path for prototypes which want every feature
+    /// enabled. Request the result with [PhysicalDevice::with_features] and
+    /// pass the device to [crate::LogicalDevice::new] so the logical device
+    /// enables all of them.
+    ///
+    /// Some optional feature structs, like robustness2, come from device
+    /// extensions. Every extension named by
+    /// [PhysicalDeviceFeatures::required_extensions] on the result must be
+    /// enabled along with the features. [crate::LogicalDevice::new] adds
+    /// them automatically.
+    pub fn all_supported_features(&self) -> PhysicalDeviceFeatures {
+        self.features
+    }
+
+    /// Which sparse resource residency layouts the device supports.
+    ///
+    /// Sparse resources also need the `sparse_binding` feature and a queue
+    /// family from [PhysicalDevice::find_sparse_binding_family].
+    pub fn sparse_properties(&self) -> &vk::PhysicalDeviceSparseProperties {
+        &self.properties().properties().sparse_properties
+    }
+
     /// The physical device name from the device properties struct.
     pub fn name(&self) -> String {
         ffi::string_from_i8(&self.properties().properties().device_name)
             .unwrap()
     }
 
+    /// The number of nanoseconds required for a timestamp query to be
+    /// incremented by 1.
+    pub fn timestamp_period(&self) -> f32 {
+        self.properties().properties().limits.timestamp_period
+    }
+
+    /// The highest sample count supported by both color and depth framebuffer
+    /// attachments.
+    ///
+    /// This is the usual choice of sample count when configuring MSAA.
+    pub fn max_usable_sample_count(&self) -> vk::SampleCountFlags {
+        let limits = &self.properties().properties().limits;
+        let counts = limits.framebuffer_color_sample_counts
+            & limits.framebuffer_depth_sample_counts;
+        [
+            vk::SampleCountFlags::TYPE_64,
+            vk::SampleCountFlags::TYPE_32,
+            vk::SampleCountFlags::TYPE_16,
+            vk::SampleCountFlags::TYPE_8,
+            vk::SampleCountFlags::TYPE_4,
+            vk::SampleCountFlags::TYPE_2,
+        ]
+        .into_iter()
+        .find(|&count| counts.contains(count))
+        .unwrap_or(vk::SampleCountFlags::TYPE_1)
+    }
+
+    /// Returns true when the device renders on the CPU, like llvmpipe or
+    /// SwiftShader.
+    ///
+    /// A device counts as a software rasterizer when its device type is
+    /// `CPU` or its name contains a well-known software renderer name. This
+    /// is useful for skipping GPU-dependent checks in CI.
+    pub fn is_software_rasterizer(&self) -> bool {
+        const SOFTWARE_RENDERER_NAMES: &[&str] =
+            &["llvmpipe", "swiftshader", "lavapipe", "softpipe"];
+
+        if self.properties().properties().device_type
+            == vk::PhysicalDeviceType::CPU
+        {
+            return true;
+        }
+        let name = self.name().to_lowercase();
+        SOFTWARE_RENDERER_NAMES
+            .iter()
+            .any(|software_name| name.contains(software_name))
+    }
+
+    /// A short, human-readable report about the device, e.g. for logs and
+    /// support tickets.
+    ///
+    /// The report includes the device's name and type, the API and driver
+    /// versions, the amount of device-local memory, and each queue family.
+    pub fn summary(&self) -> String {
+        const MIB: u64 = 1024 * 1024;
+
+        let properties = self.properties().properties();
+        let api_version = properties.api_version;
+        let mut summary = format!(
+            indoc!(
+                "
+                {} ({:?})
+                  API version: {}.{}.{}
+                  Driver version: {}
+                  Device-local memory: {} MiB"
+            ),
+            self.name(),
+            properties.device_type,
+            vk::api_version_major(api_version),
+            vk::api_version_minor(api_version),
+            vk::api_version_patch(api_version),
+            self.properties().driver_version_string(),
+            self.device_local_memory_bytes() / MIB,
+        );
+        for (index, family) in self.queue_family_properties().iter().enumerate()
+        {
+            summary.push_str(&format!(
+                "\n  Queue family {}: {} x {:?}",
+                index, family.queue_count, family.queue_flags
+            ));
+        }
+        summary
+    }
+
+    /// Count the physical devices which support the required features.
+    ///
+    /// This is cheaper than [PhysicalDevice::enumerate_supported_devices]
+    /// because only the features of each device are queried.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which will provide access to the
+    ///   physical devices.
+    /// * `features` - the features the device must support.
+    pub fn count_supported_devices(
+        instance: &VulkanInstance,
+        required_features: &PhysicalDeviceFeatures,
+    ) -> InstanceResult<usize> {
+        Ok(Self::supported_device_handles(
+            instance,
+            required_features,
+            &mut |_, _| {},
+        )?
+        .len())
+    }
+
     /// Enumerate all physical devices which support the required featuers.
     ///
+    /// Returns an empty list when devices exist but none support the
+    /// features, and [InstanceError::NoPhysicalDevices] when there are no
+    /// devices at all.
+    ///
     /// # Params
     ///
     /// * `instance` - the Vulkan instance which will provide access to the
@@ -82,63 +303,304 @@ impl PhysicalDevice {
         instance: &VulkanInstance,
         required_features: &PhysicalDeviceFeatures,
     ) -> InstanceResult<Vec<Self>> {
-        log::trace!(
-            "Looking for a device with the following features:\n{:#?}",
-            required_features
-        );
-        let all_supported_devices: Vec<vk::PhysicalDevice> =
-            unsafe { instance.ash().enumerate_physical_devices()? }
-                .into_iter()
-                .filter(|physical_device| {
-                    let available_features =
-                        PhysicalDeviceFeatures::from_physical_device(
-                            instance,
-                            physical_device,
-                        );
-                    let is_supported =
-                        required_features.is_supported_by(&available_features);
-                    log::trace!(
-                        "Physical Device {:?}\nHas All Features? {}",
-                        physical_device,
-                        is_supported,
-                    );
-                    is_supported
-                })
-                .collect();
+        Self::enumerate_supported_devices_with_rejections(
+            instance,
+            required_features,
+            &mut |_, _| {},
+        )
+    }
+
+    /// Enumerate all physical devices which support the required features,
+    /// reporting every rejected device to a callback.
+    ///
+    /// This is useful for telling the user why their GPU isn't supported.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which will provide access to the
+    ///   physical devices.
+    /// * `features` - the features the device must support.
+    /// * `on_reject` - called once for each device which doesn't support the
+    ///   required features, along with the reason it was rejected.
+    pub fn enumerate_supported_devices_with_rejections(
+        instance: &VulkanInstance,
+        required_features: &PhysicalDeviceFeatures,
+        on_reject: &mut dyn FnMut(&vk::PhysicalDevice, RejectReason),
+    ) -> InstanceResult<Vec<Self>> {
+        let all_supported_devices = Self::supported_device_handles(
+            instance,
+            required_features,
+            on_reject,
+        )?;
 
         let mut devices_with_requested_features = vec![];
-        for physical_device in all_supported_devices {
-            let properties = PhysicalDeviceProperties::from_physical_device(
+        for (physical_device, available_features) in all_supported_devices {
+            devices_with_requested_features.push(Self::new(
                 instance,
-                &physical_device,
-            );
-            let extension_properties = unsafe {
-                instance
-                    .ash()
-                    .enumerate_device_extension_properties(physical_device)?
-            };
-            let extension_names: Vec<String> = extension_properties
-                .iter()
-                .map(|props| ffi::string_from_i8(&props.extension_name))
-                .filter_map(|name| name.ok())
-                .collect();
-            let queue_family_properties = unsafe {
-                instance.ash().get_physical_device_queue_family_properties(
-                    physical_device,
-                )
-            };
-            devices_with_requested_features.push(Self {
-                properties,
-                features: *required_features,
-                available_extensions: extension_properties,
-                available_extension_names: extension_names,
-                queue_family_properties,
                 physical_device,
-            });
+                available_features,
+                *required_features,
+            )?);
         }
 
         Ok(devices_with_requested_features)
     }
+
+    /// Enumerate every physical device, regardless of which features it
+    /// supports.
+    ///
+    /// No features are requested on the returned devices. This is useful for
+    /// showing the user why their hardware isn't supported.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which will provide access to the
+    ///   physical devices.
+    pub fn enumerate_all(
+        instance: &VulkanInstance,
+    ) -> InstanceResult<Vec<Self>> {
+        let mut all_devices = vec![];
+        for physical_device in Self::enumerate_handles(instance)? {
+            let available_features =
+                PhysicalDeviceFeatures::from_physical_device(
+                    instance,
+                    &physical_device,
+                );
+            all_devices.push(Self::new(
+                instance,
+                physical_device,
+                available_features,
+                PhysicalDeviceFeatures::default(),
+            )?);
+        }
+        Ok(all_devices)
+    }
+
+    /// Enumerate every device which supports the required features, enabling
+    /// as many of the optional features as each device supports.
+    ///
+    /// This lets applications degrade gracefully on weaker hardware.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which will provide access to the
+    ///   physical devices.
+    /// * `required` - the features the device must support.
+    /// * `optional` - the features to enable when the device supports them.
+    ///
+    /// # Returns
+    ///
+    /// Each supported device along with the features it actually got, which
+    /// are also the device's requested features. Devices which support the
+    /// most optional features come first.
+    pub fn enumerate_best_effort(
+        instance: &VulkanInstance,
+        required: &PhysicalDeviceFeatures,
+        optional: &PhysicalDeviceFeatures,
+    ) -> InstanceResult<Vec<(Self, PhysicalDeviceFeatures)>> {
+        let mut candidates = vec![];
+        for (physical_device, available_features) in
+            Self::supported_device_handles(instance, required, &mut |_, _| {})?
+        {
+            let mut granted_features = *required;
+            let optional_count =
+                granted_features.add_supported(optional, &available_features);
+            let device = Self::new(
+                instance,
+                physical_device,
+                available_features,
+                granted_features,
+            )?;
+            candidates.push((optional_count, device, granted_features));
+        }
+
+        // The sort is stable, so ties keep the enumeration order.
+        candidates.sort_by(|(a, _, _), (b, _, _)| b.cmp(a));
+
+        Ok(candidates
+            .into_iter()
+            .map(|(_, device, granted_features)| (device, granted_features))
+            .collect())
+    }
+
+    /// Wrap a raw physical device handle, e.g. one provided by other Vulkan
+    /// code.
+    ///
+    /// Unlike [PhysicalDevice::enumerate_supported_devices], the device is
+    /// returned even if it doesn't support the required features. Use
+    /// `required_features.is_supported_by(device.features())` to check.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical device.
+    /// * `physical_device` - the raw physical device handle. It must have
+    ///   been enumerated from `instance`.
+    /// * `required_features` - the features to request when creating a
+    ///   logical device.
+    pub fn from_raw(
+        instance: &VulkanInstance,
+        physical_device: vk::PhysicalDevice,
+        required_features: &PhysicalDeviceFeatures,
+    ) -> InstanceResult<Self> {
+        let available_features = PhysicalDeviceFeatures::from_physical_device(
+            instance,
+            &physical_device,
+        );
+        Self::new(
+            instance,
+            physical_device,
+            available_features,
+            *required_features,
+        )
+    }
+
+    /// Query everything about a single physical device.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical device.
+    /// * `physical_device` - the raw physical device handle.
+    /// * `features` - the features supported by the device.
+    /// * `requested_features` - the features requested by the application.
+    fn new(
+        instance: &VulkanInstance,
+        physical_device: vk::PhysicalDevice,
+        features: PhysicalDeviceFeatures,
+        requested_features: PhysicalDeviceFeatures,
+    ) -> InstanceResult<Self> {
+        let properties = PhysicalDeviceProperties::from_physical_device(
+            instance,
+            &physical_device,
+        );
+        let extension_properties = unsafe {
+            instance
+                .ash()
+                .enumerate_device_extension_properties(physical_device)?
+        };
+        let extension_names: Vec<String> = extension_properties
+            .iter()
+            .map(|props| ffi::string_from_i8(&props.extension_name))
+            .filter_map(|name| name.ok())
+            .collect();
+        let (queue_family_properties, queue_family_global_priorities) =
+            Self::query_queue_families(
+                instance,
+                physical_device,
+                properties.properties().api_version,
+                &extension_names,
+            );
+        let memory_properties = unsafe {
+            instance
+                .ash()
+                .get_physical_device_memory_properties(physical_device)
+        };
+        Ok(Self {
+            properties,
+            features,
+            requested_features,
+            available_extensions: extension_properties,
+            available_extension_names: extension_names,
+            queue_family_properties,
+            queue_family_global_priorities,
+            memory_properties,
+            physical_device,
+        })
+    }
+
+    /// Get the raw handle for every physical device.
+    ///
+    /// # Returns
+    ///
+    /// Returns [InstanceError::NoPhysicalDevices] if the system has no
+    /// Vulkan devices at all.
+    fn enumerate_handles(
+        instance: &VulkanInstance,
+    ) -> InstanceResult<Vec<vk::PhysicalDevice>> {
+        let handles = unsafe { instance.ash().enumerate_physical_devices()? };
+        if handles.is_empty() {
+            Err(InstanceError::NoPhysicalDevices)
+        } else {
+            Ok(handles)
+        }
+    }
+
+    /// Get the raw handles and available features for every physical device
+    /// which supports the required features.
+    ///
+    /// `on_reject` is called for every device which doesn't.
+    fn supported_device_handles(
+        instance: &VulkanInstance,
+        required_features: &PhysicalDeviceFeatures,
+        on_reject: &mut dyn FnMut(&vk::PhysicalDevice, RejectReason),
+    ) -> InstanceResult<Vec<(vk::PhysicalDevice, PhysicalDeviceFeatures)>> {
+        log_trace!(
+            "Looking for a device with the following features:\n{:#?}",
+            required_features
+        );
+        let mut all_supported_devices = vec![];
+        for physical_device in Self::enumerate_handles(instance)? {
+            let available_features =
+                PhysicalDeviceFeatures::from_physical_device(
+                    instance,
+                    &physical_device,
+                );
+            let is_supported =
+                required_features.is_supported_by(&available_features);
+            log_trace!(
+                "Physical Device {:?}\nHas All Features? {}",
+                physical_device,
+                is_supported,
+            );
+            if is_supported {
+                all_supported_devices
+                    .push((physical_device, available_features));
+            } else {
+                let reason = Self::reject_reason(
+                    instance,
+                    &physical_device,
+                    required_features,
+                    &available_features,
+                );
+                on_reject(&physical_device, reason);
+            }
+        }
+        Ok(all_supported_devices)
+    }
+
+    /// Explain why a device doesn't support the required features.
+    ///
+    /// Missing extensions are reported first because every feature from a
+    /// missing extension is also missing.
+    fn reject_reason(
+        instance: &VulkanInstance,
+        physical_device: &vk::PhysicalDevice,
+        required_features: &PhysicalDeviceFeatures,
+        available_features: &PhysicalDeviceFeatures,
+    ) -> RejectReason {
+        let available_extensions: Vec<String> = unsafe {
+            instance
+                .ash()
+                .enumerate_device_extension_properties(*physical_device)
+                .unwrap_or_default()
+        }
+        .iter()
+        .filter_map(|props| ffi::string_from_i8(&props.extension_name).ok())
+        .collect();
+        let missing_extensions: Vec<String> = required_features
+            .required_extensions()
+            .iter()
+            .map(|name| name.to_string_lossy().to_string())
+            .filter(|name| !available_extensions.contains(name))
+            .collect();
+        if !missing_extensions.is_empty() {
+            return RejectReason::MissingExtensions(missing_extensions);
+        }
+        RejectReason::MissingFeatures(
+            required_features.missing_features(available_features),
+        )
+    }
 }
 
 impl std::fmt::Debug for PhysicalDevice {
@@ -148,8 +610,10 @@ impl std::fmt::Debug for PhysicalDevice {
                 .debug_struct("PhysicalDevice")
                 .field("properties", &self.properties)
                 .field("features", &self.features)
+                .field("requested_features", &self.requested_features)
                 .field("available_extensions", &self.available_extensions)
                 .field("queue_family_properties", &self.queue_family_properties)
+                .field("memory_properties", &self.memory_properties)
                 .finish()
         } else {
             formatter.write_str(&self.name())
@@ -167,7 +631,13 @@ impl std::fmt::Display for PhysicalDevice {
 impl VulkanHandle for PhysicalDevice {
     type Handle = vk::PhysicalDevice;
 
+    const OBJECT_TYPE: vk::ObjectType = vk::ObjectType::PHYSICAL_DEVICE;
+
     unsafe fn raw(&self) -> &Self::Handle {
         &self.physical_device
     }
+
+    unsafe fn raw_object_handle(&self) -> u64 {
+        self.physical_device.as_raw()
+    }
 }
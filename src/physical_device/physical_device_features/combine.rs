@@ -0,0 +1,89 @@
+use {crate::PhysicalDeviceFeatures, ash::vk};
+
+impl PhysicalDeviceFeatures {
+    /// Enable every feature which is enabled in `other`.
+    ///
+    /// This combines the feature requirements of independent subsystems, e.g.
+    /// a renderer and a physics engine, into a single request. Optional
+    /// structs from `other` are added to the chain when they enable any
+    /// feature.
+    ///
+    /// # Params
+    ///
+    /// * `other` - the features to add to this set.
+    pub fn merge(&mut self, other: &PhysicalDeviceFeatures) {
+        macro_rules! merge_required_feature {
+            ($($struct_path:ident).+, $feature_name:ident) => {
+                if other.$($struct_path).+.$feature_name == vk::TRUE {
+                    self.$($struct_path).+.$feature_name = vk::TRUE;
+                }
+            };
+        }
+        macro_rules! merge_optional_feature {
+            ($struct_name:ident, $feature_name:ident) => {
+                let is_enabled = other
+                    .$struct_name
+                    .map(|features| features.$feature_name == vk::TRUE)
+                    .unwrap_or(false);
+                if is_enabled {
+                    self.$struct_name
+                        .get_or_insert_with(Default::default)
+                        .$feature_name = vk::TRUE;
+                }
+            };
+        }
+
+        for_each_feature!(merge_required_feature, merge_optional_feature);
+    }
+
+    /// Enable every feature in `optional` which is also in `available`.
+    ///
+    /// # Params
+    ///
+    /// * `optional` - the features to enable if possible.
+    /// * `available` - the features supported by the device.
+    ///
+    /// # Returns
+    ///
+    /// The number of features from `optional` which are supported. Features
+    /// which were already enabled on `self` are counted too.
+    pub(crate) fn add_supported(
+        &mut self,
+        optional: &PhysicalDeviceFeatures,
+        available: &PhysicalDeviceFeatures,
+    ) -> usize {
+        let mut supported_count = 0;
+        macro_rules! add_required_feature {
+            ($($struct_path:ident).+, $feature_name:ident) => {
+                if optional.$($struct_path).+.$feature_name == vk::TRUE
+                    && available.$($struct_path).+.$feature_name == vk::TRUE
+                {
+                    self.$($struct_path).+.$feature_name = vk::TRUE;
+                    supported_count += 1;
+                }
+            };
+        }
+        macro_rules! add_optional_feature {
+            ($struct_name:ident, $feature_name:ident) => {
+                let is_requested = optional
+                    .$struct_name
+                    .map(|features| features.$feature_name == vk::TRUE)
+                    .unwrap_or(false);
+                let is_available = available
+                    .$struct_name
+                    .map(|features| features.$feature_name == vk::TRUE)
+                    .unwrap_or(false);
+                if is_requested && is_available {
+                    self.$struct_name
+                        .get_or_insert_with(Default::default)
+                        .$feature_name = vk::TRUE;
+                    supported_count += 1;
+                }
+            };
+        }
+
+        for_each_feature!(add_required_feature, add_optional_feature);
+
+        supported_count
+    }
+}
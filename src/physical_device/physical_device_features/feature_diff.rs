@@ -0,0 +1,88 @@
+use {crate::PhysicalDeviceFeatures, ash::vk};
+
+/// A comparison between requested and available features, e.g. for a
+/// settings screen which shows exactly what a GPU offers.
+///
+/// Features are identified by their Vulkan field names, like
+/// "sampler_anisotropy". Features which are neither requested nor available
+/// are not listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeatureDiff {
+    requested_and_available: Vec<&'static str>,
+    requested_but_missing: Vec<&'static str>,
+    available_but_not_requested: Vec<&'static str>,
+}
+
+impl FeatureDiff {
+    /// Features which were requested and are supported.
+    pub fn requested_and_available(&self) -> &[&'static str] {
+        &self.requested_and_available
+    }
+
+    /// Features which were requested but are not supported.
+    pub fn requested_but_missing(&self) -> &[&'static str] {
+        &self.requested_but_missing
+    }
+
+    /// Features which are supported but were not requested.
+    pub fn available_but_not_requested(&self) -> &[&'static str] {
+        &self.available_but_not_requested
+    }
+}
+
+impl PhysicalDeviceFeatures {
+    /// Compare the features enabled on this instance with the available
+    /// features.
+    ///
+    /// This is a more detailed version of
+    /// [PhysicalDeviceFeatures::is_supported_by] which checks the same
+    /// features.
+    ///
+    /// # Params
+    ///
+    /// * `available` - the available features
+    ///
+    /// # Returns
+    ///
+    /// Every feature which is requested, available, or both, sorted into the
+    /// matching category.
+    pub fn diff(&self, available: &PhysicalDeviceFeatures) -> FeatureDiff {
+        let mut diff = FeatureDiff::default();
+        let mut categorize = |name, requested: u32, is_available: u32| match (
+            requested == vk::TRUE,
+            is_available == vk::TRUE,
+        ) {
+            (true, true) => diff.requested_and_available.push(name),
+            (true, false) => diff.requested_but_missing.push(name),
+            (false, true) => diff.available_but_not_requested.push(name),
+            (false, false) => (),
+        };
+        macro_rules! diff_required_feature {
+            ($($struct_path:ident).+, $feature_name:ident) => {
+                categorize(
+                    stringify!($feature_name),
+                    self.$($struct_path).+.$feature_name,
+                    available.$($struct_path).+.$feature_name,
+                );
+            };
+        }
+        macro_rules! diff_optional_feature {
+            ($struct_name:ident, $feature_name:ident) => {
+                categorize(
+                    stringify!($feature_name),
+                    self.$struct_name
+                        .map(|features| features.$feature_name)
+                        .unwrap_or(vk::FALSE),
+                    available
+                        .$struct_name
+                        .map(|features| features.$feature_name)
+                        .unwrap_or(vk::FALSE),
+                );
+            };
+        }
+
+        for_each_feature!(diff_required_feature, diff_optional_feature);
+
+        diff
+    }
+}
@@ -0,0 +1,519 @@
+/// Invoke a macro once for every feature modeled by [PhysicalDeviceFeatures].
+///
+/// Features in the feature structs which are always present are passed to
+/// `$required` as `$required!(path.to.feature_struct, feature_name)`.
+/// Features in the optional feature structs are passed to `$optional` as
+/// `$optional!(feature_struct_field, feature_name)`.
+///
+/// Keeping the list in one place means new features only need to be added
+/// here to be picked up by every operation which visits all features.
+///
+/// [PhysicalDeviceFeatures]: crate::PhysicalDeviceFeatures
+macro_rules! for_each_feature {
+    ($required:ident, $optional:ident) => {
+        $required!(physical_device_features2.features, robust_buffer_access);
+        $required!(physical_device_features2.features, full_draw_index_uint32);
+        $required!(physical_device_features2.features, image_cube_array);
+        $required!(physical_device_features2.features, independent_blend);
+        $required!(physical_device_features2.features, geometry_shader);
+        $required!(physical_device_features2.features, tessellation_shader);
+        $required!(physical_device_features2.features, sample_rate_shading);
+        $required!(physical_device_features2.features, dual_src_blend);
+        $required!(physical_device_features2.features, logic_op);
+        $required!(physical_device_features2.features, multi_draw_indirect);
+        $required!(
+            physical_device_features2.features,
+            draw_indirect_first_instance
+        );
+        $required!(physical_device_features2.features, depth_clamp);
+        $required!(physical_device_features2.features, depth_bias_clamp);
+        $required!(physical_device_features2.features, fill_mode_non_solid);
+        $required!(physical_device_features2.features, depth_bounds);
+        $required!(physical_device_features2.features, wide_lines);
+        $required!(physical_device_features2.features, large_points);
+        $required!(physical_device_features2.features, alpha_to_one);
+        $required!(physical_device_features2.features, multi_viewport);
+        $required!(physical_device_features2.features, sampler_anisotropy);
+        $required!(
+            physical_device_features2.features,
+            texture_compression_etc2
+        );
+        $required!(
+            physical_device_features2.features,
+            texture_compression_astc_ldr
+        );
+        $required!(physical_device_features2.features, texture_compression_bc);
+        $required!(physical_device_features2.features, occlusion_query_precise);
+        $required!(
+            physical_device_features2.features,
+            pipeline_statistics_query
+        );
+        $required!(
+            physical_device_features2.features,
+            vertex_pipeline_stores_and_atomics
+        );
+        $required!(
+            physical_device_features2.features,
+            fragment_stores_and_atomics
+        );
+        $required!(
+            physical_device_features2.features,
+            shader_tessellation_and_geometry_point_size
+        );
+        $required!(
+            physical_device_features2.features,
+            shader_image_gather_extended
+        );
+        $required!(
+            physical_device_features2.features,
+            shader_storage_image_extended_formats
+        );
+        $required!(
+            physical_device_features2.features,
+            shader_storage_image_multisample
+        );
+        $required!(
+            physical_device_features2.features,
+            shader_storage_image_read_without_format
+        );
+        $required!(
+            physical_device_features2.features,
+            shader_storage_image_write_without_format
+        );
+        $required!(
+            physical_device_features2.features,
+            shader_uniform_buffer_array_dynamic_indexing
+        );
+        $required!(
+            physical_device_features2.features,
+            shader_sampled_image_array_dynamic_indexing
+        );
+        $required!(
+            physical_device_features2.features,
+            shader_storage_buffer_array_dynamic_indexing
+        );
+        $required!(
+            physical_device_features2.features,
+            shader_storage_image_array_dynamic_indexing
+        );
+        $required!(physical_device_features2.features, shader_clip_distance);
+        $required!(physical_device_features2.features, shader_cull_distance);
+        $required!(physical_device_features2.features, shader_float64);
+        $required!(physical_device_features2.features, shader_int64);
+        $required!(physical_device_features2.features, shader_int16);
+        $required!(
+            physical_device_features2.features,
+            shader_resource_residency
+        );
+        $required!(physical_device_features2.features, shader_resource_min_lod);
+        $required!(physical_device_features2.features, sparse_binding);
+        $required!(physical_device_features2.features, sparse_residency_buffer);
+        $required!(
+            physical_device_features2.features,
+            sparse_residency_image2_d
+        );
+        $required!(
+            physical_device_features2.features,
+            sparse_residency_image3_d
+        );
+        $required!(
+            physical_device_features2.features,
+            sparse_residency2_samples
+        );
+        $required!(
+            physical_device_features2.features,
+            sparse_residency4_samples
+        );
+        $required!(
+            physical_device_features2.features,
+            sparse_residency8_samples
+        );
+        $required!(
+            physical_device_features2.features,
+            sparse_residency16_samples
+        );
+        $required!(
+            physical_device_features2.features,
+            sparse_residency_aliased
+        );
+        $required!(
+            physical_device_features2.features,
+            variable_multisample_rate
+        );
+        $required!(physical_device_features2.features, inherited_queries);
+
+        $required!(physical_device_vulkan_13_features, robust_image_access);
+        $required!(physical_device_vulkan_13_features, inline_uniform_block);
+        $required!(
+            physical_device_vulkan_13_features,
+            descriptor_binding_inline_uniform_block_update_after_bind
+        );
+        $required!(
+            physical_device_vulkan_13_features,
+            pipeline_creation_cache_control
+        );
+        $required!(physical_device_vulkan_13_features, private_data);
+        $required!(
+            physical_device_vulkan_13_features,
+            shader_demote_to_helper_invocation
+        );
+        $required!(
+            physical_device_vulkan_13_features,
+            shader_terminate_invocation
+        );
+        $required!(physical_device_vulkan_13_features, subgroup_size_control);
+        $required!(physical_device_vulkan_13_features, compute_full_subgroups);
+        $required!(physical_device_vulkan_13_features, synchronization2);
+        $required!(
+            physical_device_vulkan_13_features,
+            texture_compression_astc_hdr
+        );
+        $required!(
+            physical_device_vulkan_13_features,
+            shader_zero_initialize_workgroup_memory
+        );
+        $required!(physical_device_vulkan_13_features, dynamic_rendering);
+        $required!(
+            physical_device_vulkan_13_features,
+            shader_integer_dot_product
+        );
+        $required!(physical_device_vulkan_13_features, maintenance4);
+
+        $required!(
+            descriptor_indexing_features,
+            shader_input_attachment_array_dynamic_indexing
+        );
+        $required!(
+            descriptor_indexing_features,
+            shader_uniform_texel_buffer_array_dynamic_indexing
+        );
+        $required!(
+            descriptor_indexing_features,
+            shader_storage_texel_buffer_array_dynamic_indexing
+        );
+        $required!(
+            descriptor_indexing_features,
+            shader_uniform_buffer_array_non_uniform_indexing
+        );
+        $required!(
+            descriptor_indexing_features,
+            shader_sampled_image_array_non_uniform_indexing
+        );
+        $required!(
+            descriptor_indexing_features,
+            shader_storage_buffer_array_non_uniform_indexing
+        );
+        $required!(
+            descriptor_indexing_features,
+            shader_storage_image_array_non_uniform_indexing
+        );
+        $required!(
+            descriptor_indexing_features,
+            shader_input_attachment_array_non_uniform_indexing
+        );
+        $required!(
+            descriptor_indexing_features,
+            shader_uniform_texel_buffer_array_non_uniform_indexing
+        );
+        $required!(
+            descriptor_indexing_features,
+            shader_storage_texel_buffer_array_non_uniform_indexing
+        );
+        $required!(
+            descriptor_indexing_features,
+            descriptor_binding_uniform_buffer_update_after_bind
+        );
+        $required!(
+            descriptor_indexing_features,
+            descriptor_binding_sampled_image_update_after_bind
+        );
+        $required!(
+            descriptor_indexing_features,
+            descriptor_binding_storage_image_update_after_bind
+        );
+        $required!(
+            descriptor_indexing_features,
+            descriptor_binding_storage_buffer_update_after_bind
+        );
+        $required!(
+            descriptor_indexing_features,
+            descriptor_binding_uniform_texel_buffer_update_after_bind
+        );
+        $required!(
+            descriptor_indexing_features,
+            descriptor_binding_storage_texel_buffer_update_after_bind
+        );
+        $required!(
+            descriptor_indexing_features,
+            descriptor_binding_update_unused_while_pending
+        );
+        $required!(
+            descriptor_indexing_features,
+            descriptor_binding_partially_bound
+        );
+        $required!(
+            descriptor_indexing_features,
+            descriptor_binding_variable_descriptor_count
+        );
+        $required!(descriptor_indexing_features, runtime_descriptor_array);
+
+        $optional!(storage_8bit_features, storage_buffer8_bit_access);
+        $optional!(
+            storage_8bit_features,
+            uniform_and_storage_buffer8_bit_access
+        );
+        $optional!(storage_8bit_features, storage_push_constant8);
+
+        $optional!(storage_16bit_features, storage_buffer16_bit_access);
+        $optional!(
+            storage_16bit_features,
+            uniform_and_storage_buffer16_bit_access
+        );
+        $optional!(storage_16bit_features, storage_push_constant16);
+        $optional!(storage_16bit_features, storage_input_output16);
+
+        $optional!(robustness2_features, robust_buffer_access2);
+        $optional!(robustness2_features, robust_image_access2);
+        $optional!(robustness2_features, null_descriptor);
+
+        $optional!(protected_memory_features, protected_memory);
+
+        $optional!(host_query_reset_features, host_query_reset);
+
+        $optional!(conditional_rendering_features, conditional_rendering);
+        $optional!(
+            conditional_rendering_features,
+            inherited_conditional_rendering
+        );
+
+        $optional!(descriptor_buffer_features, descriptor_buffer);
+        $optional!(
+            descriptor_buffer_features,
+            descriptor_buffer_capture_replay
+        );
+        $optional!(
+            descriptor_buffer_features,
+            descriptor_buffer_image_layout_ignored
+        );
+        $optional!(
+            descriptor_buffer_features,
+            descriptor_buffer_push_descriptors
+        );
+
+        $optional!(
+            fragment_shading_rate_features,
+            pipeline_fragment_shading_rate
+        );
+        $optional!(
+            fragment_shading_rate_features,
+            primitive_fragment_shading_rate
+        );
+        $optional!(
+            fragment_shading_rate_features,
+            attachment_fragment_shading_rate
+        );
+
+        $optional!(multiview_features, multiview);
+        $optional!(multiview_features, multiview_geometry_shader);
+        $optional!(multiview_features, multiview_tessellation_shader);
+
+        $optional!(shader_draw_parameters_features, shader_draw_parameters);
+
+        $optional!(shader_object_features, shader_object);
+
+        $optional!(shader_atomic_float_features, shader_buffer_float32_atomics);
+        $optional!(
+            shader_atomic_float_features,
+            shader_buffer_float32_atomic_add
+        );
+        $optional!(shader_atomic_float_features, shader_buffer_float64_atomics);
+        $optional!(
+            shader_atomic_float_features,
+            shader_buffer_float64_atomic_add
+        );
+        $optional!(shader_atomic_float_features, shader_shared_float32_atomics);
+        $optional!(
+            shader_atomic_float_features,
+            shader_shared_float32_atomic_add
+        );
+        $optional!(shader_atomic_float_features, shader_shared_float64_atomics);
+        $optional!(
+            shader_atomic_float_features,
+            shader_shared_float64_atomic_add
+        );
+        $optional!(shader_atomic_float_features, shader_image_float32_atomics);
+        $optional!(
+            shader_atomic_float_features,
+            shader_image_float32_atomic_add
+        );
+        $optional!(shader_atomic_float_features, sparse_image_float32_atomics);
+        $optional!(
+            shader_atomic_float_features,
+            sparse_image_float32_atomic_add
+        );
+
+        $optional!(cooperative_matrix_features, cooperative_matrix);
+        $optional!(
+            cooperative_matrix_features,
+            cooperative_matrix_robust_buffer_access
+        );
+
+        $optional!(present_id_features, present_id);
+
+        $optional!(present_wait_features, present_wait);
+
+        $optional!(
+            vertex_attribute_divisor_features,
+            vertex_attribute_instance_rate_divisor
+        );
+        $optional!(
+            vertex_attribute_divisor_features,
+            vertex_attribute_zero_divisor
+        );
+
+        $optional!(extended_dynamic_state_features, extended_dynamic_state);
+
+        $optional!(extended_dynamic_state2_features, extended_dynamic_state2);
+        $optional!(
+            extended_dynamic_state2_features,
+            extended_dynamic_state2_logic_op
+        );
+        $optional!(
+            extended_dynamic_state2_features,
+            extended_dynamic_state2_patch_control_points
+        );
+
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_tessellation_domain_origin
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_depth_clamp_enable
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_polygon_mode
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_rasterization_samples
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_sample_mask
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_alpha_to_coverage_enable
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_alpha_to_one_enable
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_logic_op_enable
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_color_blend_enable
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_color_blend_equation
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_color_write_mask
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_rasterization_stream
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_conservative_rasterization_mode
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_extra_primitive_overestimation_size
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_depth_clip_enable
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_sample_locations_enable
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_color_blend_advanced
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_provoking_vertex_mode
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_line_rasterization_mode
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_line_stipple_enable
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_depth_clip_negative_one_to_one
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_viewport_w_scaling_enable
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_viewport_swizzle
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_coverage_to_color_enable
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_coverage_to_color_location
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_coverage_modulation_mode
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_coverage_modulation_table_enable
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_coverage_modulation_table
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_coverage_reduction_mode
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_representative_fragment_test_enable
+        );
+        $optional!(
+            extended_dynamic_state3_features,
+            extended_dynamic_state3_shading_rate_image_enable
+        );
+
+        $optional!(provoking_vertex_features, provoking_vertex_last);
+        $optional!(
+            provoking_vertex_features,
+            transform_feedback_preserves_provoking_vertex
+        );
+
+        $optional!(depth_clip_control_features, depth_clip_control);
+    };
+}
@@ -0,0 +1,31 @@
+use {crate::PhysicalDeviceFeatures, ash::vk};
+
+impl PhysicalDeviceFeatures {
+    /// The names of every feature which is set to `vk::TRUE`.
+    ///
+    /// Features in optional structs are only included when the struct is
+    /// present.
+    pub(crate) fn enabled_feature_names(&self) -> Vec<&'static str> {
+        let mut names = vec![];
+        macro_rules! push_required_feature {
+            ($($struct_path:ident).+, $feature_name:ident) => {
+                if self.$($struct_path).+.$feature_name == vk::TRUE {
+                    names.push(stringify!($feature_name));
+                }
+            };
+        }
+        macro_rules! push_optional_feature {
+            ($struct_name:ident, $feature_name:ident) => {
+                if let Some(features) = &self.$struct_name {
+                    if features.$feature_name == vk::TRUE {
+                        names.push(stringify!($feature_name));
+                    }
+                }
+            };
+        }
+
+        for_each_feature!(push_required_feature, push_optional_feature);
+
+        names
+    }
+}
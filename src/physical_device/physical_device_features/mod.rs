@@ -1,6 +1,23 @@
-use {crate::VulkanInstance, ash::vk, std::ffi::c_void};
+use {
+    crate::{ffi, VulkanInstance},
+    ash::vk,
+    std::{
+        ffi::{c_void, CStr},
+        ptr::NonNull,
+    },
+};
 
+#[macro_use]
+mod feature_list;
+
+mod combine;
+mod feature_diff;
+#[cfg(feature = "json")]
+mod feature_names;
 mod is_supported_by;
+mod required_extensions;
+
+pub use self::feature_diff::FeatureDiff;
 
 /// An owned set of physical device features.
 #[derive(Copy, Clone, Debug, Default)]
@@ -8,6 +25,50 @@ pub struct PhysicalDeviceFeatures {
     physical_device_features2: vk::PhysicalDeviceFeatures2,
     descriptor_indexing_features: vk::PhysicalDeviceDescriptorIndexingFeatures,
     physical_device_vulkan_13_features: vk::PhysicalDeviceVulkan13Features,
+
+    // Optional feature structs are only linked into the p_next chain when
+    // present.
+    storage_8bit_features: Option<vk::PhysicalDevice8BitStorageFeatures>,
+    storage_16bit_features: Option<vk::PhysicalDevice16BitStorageFeatures>,
+    robustness2_features: Option<vk::PhysicalDeviceRobustness2FeaturesEXT>,
+    protected_memory_features:
+        Option<vk::PhysicalDeviceProtectedMemoryFeatures>,
+    host_query_reset_features: Option<vk::PhysicalDeviceHostQueryResetFeatures>,
+    conditional_rendering_features:
+        Option<vk::PhysicalDeviceConditionalRenderingFeaturesEXT>,
+    descriptor_buffer_features:
+        Option<vk::PhysicalDeviceDescriptorBufferFeaturesEXT>,
+    fragment_shading_rate_features:
+        Option<vk::PhysicalDeviceFragmentShadingRateFeaturesKHR>,
+    multiview_features: Option<vk::PhysicalDeviceMultiviewFeatures>,
+    shader_draw_parameters_features:
+        Option<vk::PhysicalDeviceShaderDrawParametersFeatures>,
+    shader_object_features: Option<vk::PhysicalDeviceShaderObjectFeaturesEXT>,
+    shader_atomic_float_features:
+        Option<vk::PhysicalDeviceShaderAtomicFloatFeaturesEXT>,
+    cooperative_matrix_features:
+        Option<vk::PhysicalDeviceCooperativeMatrixFeaturesNV>,
+    present_id_features: Option<vk::PhysicalDevicePresentIdFeaturesKHR>,
+    present_wait_features: Option<vk::PhysicalDevicePresentWaitFeaturesKHR>,
+    vertex_attribute_divisor_features:
+        Option<vk::PhysicalDeviceVertexAttributeDivisorFeaturesEXT>,
+    extended_dynamic_state_features:
+        Option<vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT>,
+    extended_dynamic_state2_features:
+        Option<vk::PhysicalDeviceExtendedDynamicState2FeaturesEXT>,
+    extended_dynamic_state3_features:
+        Option<vk::PhysicalDeviceExtendedDynamicState3FeaturesEXT>,
+    provoking_vertex_features:
+        Option<vk::PhysicalDeviceProvokingVertexFeaturesEXT>,
+    depth_clip_control_features:
+        Option<vk::PhysicalDeviceDepthClipControlFeaturesEXT>,
+
+    // A user-provided chain of feature structs, linked after everything else.
+    extra_p_next: Option<NonNull<c_void>>,
+
+    // True when the Vulkan 1.3 struct must be left out of the p_next chain
+    // because the device or instance predates Vulkan 1.3.
+    omit_vulkan_13_features: bool,
 }
 
 impl PhysicalDeviceFeatures {
@@ -18,11 +79,109 @@ impl PhysicalDeviceFeatures {
     /// * `instance` - the instance which provides access to the physical device
     /// * `physical_device` - the physical device to query for available
     ///   features
+    ///
+    /// Optional feature structs are only queried when the API version or
+    /// available extensions support them. The API version is the older of
+    /// the device's and the instance's versions. Only the core Vulkan 1.0
+    /// features are queried when either predates Vulkan 1.1.
     pub fn from_physical_device(
         instance: &VulkanInstance,
         physical_device: &vk::PhysicalDevice,
     ) -> PhysicalDeviceFeatures {
+        let device_api_version = unsafe {
+            instance
+                .ash()
+                .get_physical_device_properties(*physical_device)
+                .api_version
+        };
+        let api_version = device_api_version.min(instance.api_version());
+
+        // The *2 queries, and every chained struct, need Vulkan 1.1 support
+        // from both the instance and the device.
+        if api_version < vk::API_VERSION_1_1 {
+            let mut results = Self::default();
+            results.physical_device_features2.features = unsafe {
+                instance
+                    .ash()
+                    .get_physical_device_features(*physical_device)
+            };
+            return results;
+        }
+
+        let extension_names: Vec<String> = unsafe {
+            instance
+                .ash()
+                .enumerate_device_extension_properties(*physical_device)
+                .unwrap_or_default()
+        }
+        .iter()
+        .map(|props| ffi::string_from_i8(&props.extension_name))
+        .filter_map(|name| name.ok())
+        .collect();
+        let has_extension = |name: &CStr| {
+            extension_names
+                .iter()
+                .any(|available| Some(available.as_str()) == name.to_str().ok())
+        };
+
         let mut results = PhysicalDeviceFeatures::default();
+        results.omit_vulkan_13_features = api_version < vk::API_VERSION_1_3;
+        if api_version >= vk::API_VERSION_1_1 {
+            results.storage_16bit_features = Some(Default::default());
+            results.protected_memory_features = Some(Default::default());
+            results.multiview_features = Some(Default::default());
+            results.shader_draw_parameters_features = Some(Default::default());
+        }
+        if api_version >= vk::API_VERSION_1_2 {
+            results.storage_8bit_features = Some(Default::default());
+            results.host_query_reset_features = Some(Default::default());
+        }
+        if has_extension(vk::ExtRobustness2Fn::name()) {
+            results.robustness2_features = Some(Default::default());
+        }
+        if has_extension(vk::ExtConditionalRenderingFn::name()) {
+            results.conditional_rendering_features = Some(Default::default());
+        }
+        if has_extension(vk::ExtDescriptorBufferFn::name()) {
+            results.descriptor_buffer_features = Some(Default::default());
+        }
+        if has_extension(vk::KhrFragmentShadingRateFn::name()) {
+            results.fragment_shading_rate_features = Some(Default::default());
+        }
+        if has_extension(vk::ExtShaderObjectFn::name()) {
+            results.shader_object_features = Some(Default::default());
+        }
+        if has_extension(vk::ExtShaderAtomicFloatFn::name()) {
+            results.shader_atomic_float_features = Some(Default::default());
+        }
+        if has_extension(vk::NvCooperativeMatrixFn::name()) {
+            results.cooperative_matrix_features = Some(Default::default());
+        }
+        if has_extension(vk::KhrPresentIdFn::name()) {
+            results.present_id_features = Some(Default::default());
+        }
+        if has_extension(vk::KhrPresentWaitFn::name()) {
+            results.present_wait_features = Some(Default::default());
+        }
+        if has_extension(vk::ExtVertexAttributeDivisorFn::name()) {
+            results.vertex_attribute_divisor_features =
+                Some(Default::default());
+        }
+        if has_extension(vk::ExtExtendedDynamicStateFn::name()) {
+            results.extended_dynamic_state_features = Some(Default::default());
+        }
+        if has_extension(vk::ExtExtendedDynamicState2Fn::name()) {
+            results.extended_dynamic_state2_features = Some(Default::default());
+        }
+        if has_extension(vk::ExtExtendedDynamicState3Fn::name()) {
+            results.extended_dynamic_state3_features = Some(Default::default());
+        }
+        if has_extension(vk::ExtProvokingVertexFn::name()) {
+            results.provoking_vertex_features = Some(Default::default());
+        }
+        if has_extension(vk::ExtDepthClipControlFn::name()) {
+            results.depth_clip_control_features = Some(Default::default());
+        }
         unsafe {
             instance.ash().get_physical_device_features2(
                 *physical_device,
@@ -62,6 +221,588 @@ impl PhysicalDeviceFeatures {
         &mut self.physical_device_vulkan_13_features
     }
 
+    /// The 8-bit storage features. `None` when the struct is not part of the
+    /// p_next chain, e.g. when the device predates Vulkan 1.2.
+    pub fn storage_8bit_features(
+        &self,
+    ) -> Option<&vk::PhysicalDevice8BitStorageFeatures> {
+        self.storage_8bit_features.as_ref()
+    }
+
+    /// Mutable access to the 8-bit storage features. The struct is added to
+    /// the p_next chain if it wasn't already present.
+    pub fn storage_8bit_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDevice8BitStorageFeatures {
+        self.storage_8bit_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The 16-bit storage features. `None` when the struct is not part of
+    /// the p_next chain, e.g. when the device predates Vulkan 1.1.
+    pub fn storage_16bit_features(
+        &self,
+    ) -> Option<&vk::PhysicalDevice16BitStorageFeatures> {
+        self.storage_16bit_features.as_ref()
+    }
+
+    /// Mutable access to the 16-bit storage features. The struct is added to
+    /// the p_next chain if it wasn't already present.
+    pub fn storage_16bit_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDevice16BitStorageFeatures {
+        self.storage_16bit_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The robustness2 features. `None` when the struct is not part of the
+    /// p_next chain, e.g. when the device doesn't support the
+    /// VK_EXT_robustness2 extension.
+    pub fn robustness2_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceRobustness2FeaturesEXT> {
+        self.robustness2_features.as_ref()
+    }
+
+    /// Mutable access to the robustness2 features. The struct is added to
+    /// the p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the VK_EXT_robustness2 device
+    /// extension.
+    pub fn robustness2_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceRobustness2FeaturesEXT {
+        self.robustness2_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The protected memory features. `None` when the struct is not part of
+    /// the p_next chain, e.g. when the device predates Vulkan 1.1.
+    pub fn protected_memory_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceProtectedMemoryFeatures> {
+        self.protected_memory_features.as_ref()
+    }
+
+    /// Mutable access to the protected memory features. The struct is added
+    /// to the p_next chain if it wasn't already present.
+    pub fn protected_memory_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceProtectedMemoryFeatures {
+        self.protected_memory_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The baseline features for a Vulkan 1.3 renderer: synchronization2,
+    /// dynamic_rendering, and maintenance4.
+    ///
+    /// Other `with_*` presets can be chained before the result is used to
+    /// pick a device.
+    pub fn with_modern_rendering(mut self) -> Self {
+        let vulkan_13_features = self.vulkan_13_features_mut();
+        vulkan_13_features.synchronization2 = vk::TRUE;
+        vulkan_13_features.dynamic_rendering = vk::TRUE;
+        vulkan_13_features.maintenance4 = vk::TRUE;
+        self
+    }
+
+    /// Enable every descriptor indexing feature needed for bindless
+    /// resources.
+    ///
+    /// The following descriptor indexing fields are set to `vk::TRUE`:
+    ///
+    /// * `shader_uniform_buffer_array_non_uniform_indexing`
+    /// * `shader_sampled_image_array_non_uniform_indexing`
+    /// * `shader_storage_buffer_array_non_uniform_indexing`
+    /// * `shader_storage_image_array_non_uniform_indexing`
+    /// * `shader_input_attachment_array_non_uniform_indexing`
+    /// * `shader_uniform_texel_buffer_array_non_uniform_indexing`
+    /// * `shader_storage_texel_buffer_array_non_uniform_indexing`
+    /// * `descriptor_binding_uniform_buffer_update_after_bind`
+    /// * `descriptor_binding_sampled_image_update_after_bind`
+    /// * `descriptor_binding_storage_image_update_after_bind`
+    /// * `descriptor_binding_storage_buffer_update_after_bind`
+    /// * `descriptor_binding_uniform_texel_buffer_update_after_bind`
+    /// * `descriptor_binding_storage_texel_buffer_update_after_bind`
+    /// * `descriptor_binding_update_unused_while_pending`
+    /// * `descriptor_binding_partially_bound`
+    /// * `descriptor_binding_variable_descriptor_count`
+    /// * `runtime_descriptor_array`
+    ///
+    /// Devices commonly lack a few of these, e.g. uniform buffer update after
+    /// bind. Clear the offending fields afterwards if a device is rejected.
+    pub fn with_full_descriptor_indexing(mut self) -> Self {
+        let indexing = &mut self.descriptor_indexing_features;
+        indexing.shader_uniform_buffer_array_non_uniform_indexing = vk::TRUE;
+        indexing.shader_sampled_image_array_non_uniform_indexing = vk::TRUE;
+        indexing.shader_storage_buffer_array_non_uniform_indexing = vk::TRUE;
+        indexing.shader_storage_image_array_non_uniform_indexing = vk::TRUE;
+        indexing.shader_input_attachment_array_non_uniform_indexing = vk::TRUE;
+        indexing.shader_uniform_texel_buffer_array_non_uniform_indexing =
+            vk::TRUE;
+        indexing.shader_storage_texel_buffer_array_non_uniform_indexing =
+            vk::TRUE;
+        indexing.descriptor_binding_uniform_buffer_update_after_bind = vk::TRUE;
+        indexing.descriptor_binding_sampled_image_update_after_bind = vk::TRUE;
+        indexing.descriptor_binding_storage_image_update_after_bind = vk::TRUE;
+        indexing.descriptor_binding_storage_buffer_update_after_bind = vk::TRUE;
+        indexing.descriptor_binding_uniform_texel_buffer_update_after_bind =
+            vk::TRUE;
+        indexing.descriptor_binding_storage_texel_buffer_update_after_bind =
+            vk::TRUE;
+        indexing.descriptor_binding_update_unused_while_pending = vk::TRUE;
+        indexing.descriptor_binding_partially_bound = vk::TRUE;
+        indexing.descriptor_binding_variable_descriptor_count = vk::TRUE;
+        indexing.runtime_descriptor_array = vk::TRUE;
+        self
+    }
+
+    /// Enable null descriptors so unbound descriptors have well-defined
+    /// behavior. This is useful for bindless and streaming-asset pipelines.
+    ///
+    /// Requires the VK_EXT_robustness2 device extension.
+    pub fn with_null_descriptor(mut self) -> Self {
+        self.robustness2_features_mut().null_descriptor = vk::TRUE;
+        self
+    }
+
+    /// Enable 8-bit and 16-bit access to storage and uniform buffers.
+    ///
+    /// Useful for compute shaders which pack data into small types.
+    pub fn with_small_storage(mut self) -> Self {
+        let storage_8bit = self.storage_8bit_features_mut();
+        storage_8bit.storage_buffer8_bit_access = vk::TRUE;
+        storage_8bit.uniform_and_storage_buffer8_bit_access = vk::TRUE;
+
+        let storage_16bit = self.storage_16bit_features_mut();
+        storage_16bit.storage_buffer16_bit_access = vk::TRUE;
+        storage_16bit.uniform_and_storage_buffer16_bit_access = vk::TRUE;
+
+        self
+    }
+
+    /// The host query reset features. `None` when the struct is not part of the
+    /// p_next chain, e.g. when the device predates Vulkan 1.2.
+    pub fn host_query_reset_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceHostQueryResetFeatures> {
+        self.host_query_reset_features.as_ref()
+    }
+
+    /// Mutable access to the host query reset features. The struct is added to
+    /// the p_next chain if it wasn't already present.
+    pub fn host_query_reset_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceHostQueryResetFeatures {
+        self.host_query_reset_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// Require support for resetting query pools from the host, without a
+    /// command buffer.
+    pub fn with_host_query_reset(mut self) -> Self {
+        self.host_query_reset_features_mut().host_query_reset = vk::TRUE;
+        self
+    }
+
+    /// The conditional rendering features. `None` when the struct is not part
+    /// of the p_next chain, e.g. when the device doesn't support the
+    /// VK_EXT_conditional_rendering extension.
+    pub fn conditional_rendering_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceConditionalRenderingFeaturesEXT> {
+        self.conditional_rendering_features.as_ref()
+    }
+
+    /// Mutable access to the conditional rendering features. The struct is
+    /// added to the p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the VK_EXT_conditional_rendering
+    /// device extension.
+    pub fn conditional_rendering_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceConditionalRenderingFeaturesEXT {
+        self.conditional_rendering_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The descriptor buffer features. `None` when the struct is not part of
+    /// the p_next chain, e.g. when the device doesn't support the
+    /// VK_EXT_descriptor_buffer extension.
+    pub fn descriptor_buffer_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceDescriptorBufferFeaturesEXT> {
+        self.descriptor_buffer_features.as_ref()
+    }
+
+    /// Mutable access to the descriptor buffer features. The struct is added to
+    /// the p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the VK_EXT_descriptor_buffer
+    /// device extension.
+    pub fn descriptor_buffer_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceDescriptorBufferFeaturesEXT {
+        self.descriptor_buffer_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The fragment shading rate features. `None` when the struct is not part
+    /// of the p_next chain, e.g. when the device doesn't support the
+    /// VK_KHR_fragment_shading_rate extension.
+    pub fn fragment_shading_rate_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceFragmentShadingRateFeaturesKHR> {
+        self.fragment_shading_rate_features.as_ref()
+    }
+
+    /// Mutable access to the fragment shading rate features. The struct is
+    /// added to the p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the VK_KHR_fragment_shading_rate
+    /// device extension.
+    pub fn fragment_shading_rate_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceFragmentShadingRateFeaturesKHR {
+        self.fragment_shading_rate_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The multiview features. `None` when the struct is not part of the p_next
+    /// chain, e.g. when the device predates Vulkan 1.1.
+    pub fn multiview_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceMultiviewFeatures> {
+        self.multiview_features.as_ref()
+    }
+
+    /// Mutable access to the multiview features. The struct is added to the
+    /// p_next chain if it wasn't already present.
+    pub fn multiview_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceMultiviewFeatures {
+        self.multiview_features.get_or_insert_with(Default::default)
+    }
+
+    /// The shader draw parameters features. `None` when the struct is not part
+    /// of the p_next chain, e.g. when the device predates Vulkan 1.1.
+    pub fn shader_draw_parameters_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceShaderDrawParametersFeatures> {
+        self.shader_draw_parameters_features.as_ref()
+    }
+
+    /// Mutable access to the shader draw parameters features. The struct is
+    /// added to the p_next chain if it wasn't already present.
+    pub fn shader_draw_parameters_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceShaderDrawParametersFeatures {
+        self.shader_draw_parameters_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// Require support for rendering to multiple views, e.g. both eyes of an
+    /// XR headset, in a single render pass.
+    pub fn with_multiview(mut self) -> Self {
+        self.multiview_features_mut().multiview = vk::TRUE;
+        self
+    }
+
+    /// The shader object features. `None` when the struct is not part of the
+    /// p_next chain, e.g. when the device doesn't support the
+    /// VK_EXT_shader_object extension.
+    pub fn shader_object_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceShaderObjectFeaturesEXT> {
+        self.shader_object_features.as_ref()
+    }
+
+    /// Mutable access to the shader object features. The struct is added to the
+    /// p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the VK_EXT_shader_object device
+    /// extension.
+    ///
+    /// Shader objects replace pipeline objects, so pipeline caches and
+    /// pipeline libraries don't apply to them. Every piece of pipeline state
+    /// must be set dynamically instead.
+    pub fn shader_object_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceShaderObjectFeaturesEXT {
+        self.shader_object_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The shader atomic float features. `None` when the struct is not part of
+    /// the p_next chain, e.g. when the device doesn't support the
+    /// VK_EXT_shader_atomic_float extension.
+    pub fn shader_atomic_float_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceShaderAtomicFloatFeaturesEXT> {
+        self.shader_atomic_float_features.as_ref()
+    }
+
+    /// Mutable access to the shader atomic float features. The struct is added
+    /// to the p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the VK_EXT_shader_atomic_float
+    /// device extension.
+    pub fn shader_atomic_float_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceShaderAtomicFloatFeaturesEXT {
+        self.shader_atomic_float_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// Require 32-bit floating point atomic loads, stores, exchanges, and adds
+    /// on storage buffers and shared memory, e.g. for compute reductions.
+    ///
+    /// This requires the VK_EXT_shader_atomic_float device extension.
+    pub fn with_float32_atomic_add(mut self) -> Self {
+        let atomic_float = self.shader_atomic_float_features_mut();
+        atomic_float.shader_buffer_float32_atomics = vk::TRUE;
+        atomic_float.shader_buffer_float32_atomic_add = vk::TRUE;
+        atomic_float.shader_shared_float32_atomics = vk::TRUE;
+        atomic_float.shader_shared_float32_atomic_add = vk::TRUE;
+        self
+    }
+
+    /// The cooperative matrix features. `None` when the struct is not part of
+    /// the p_next chain, e.g. when the device doesn't support the
+    /// VK_NV_cooperative_matrix extension.
+    pub fn cooperative_matrix_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceCooperativeMatrixFeaturesNV> {
+        self.cooperative_matrix_features.as_ref()
+    }
+
+    /// Mutable access to the cooperative matrix features. The struct is added
+    /// to the p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the VK_NV_cooperative_matrix
+    /// device extension.
+    pub fn cooperative_matrix_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceCooperativeMatrixFeaturesNV {
+        self.cooperative_matrix_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The present id features. `None` when the struct is not part of the
+    /// p_next chain, e.g. when the device doesn't support the VK_KHR_present_id
+    /// extension.
+    pub fn present_id_features(
+        &self,
+    ) -> Option<&vk::PhysicalDevicePresentIdFeaturesKHR> {
+        self.present_id_features.as_ref()
+    }
+
+    /// Mutable access to the present id features. The struct is added to the
+    /// p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the VK_KHR_present_id device
+    /// extension.
+    ///
+    /// VK_KHR_swapchain is a prerequisite of the extension.
+    pub fn present_id_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDevicePresentIdFeaturesKHR {
+        self.present_id_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The present wait features. `None` when the struct is not part of the
+    /// p_next chain, e.g. when the device doesn't support the
+    /// VK_KHR_present_wait extension.
+    pub fn present_wait_features(
+        &self,
+    ) -> Option<&vk::PhysicalDevicePresentWaitFeaturesKHR> {
+        self.present_wait_features.as_ref()
+    }
+
+    /// Mutable access to the present wait features. The struct is added to the
+    /// p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the VK_KHR_present_wait device
+    /// extension.
+    ///
+    /// VK_KHR_swapchain and VK_KHR_present_id are prerequisites of the
+    /// extension.
+    pub fn present_wait_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDevicePresentWaitFeaturesKHR {
+        self.present_wait_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The vertex attribute divisor features. `None` when the struct is not
+    /// part of the p_next chain, e.g. when the device doesn't support the
+    /// VK_EXT_vertex_attribute_divisor extension.
+    pub fn vertex_attribute_divisor_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceVertexAttributeDivisorFeaturesEXT> {
+        self.vertex_attribute_divisor_features.as_ref()
+    }
+
+    /// Mutable access to the vertex attribute divisor features. The struct is
+    /// added to the p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the
+    /// VK_EXT_vertex_attribute_divisor device extension.
+    pub fn vertex_attribute_divisor_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceVertexAttributeDivisorFeaturesEXT {
+        self.vertex_attribute_divisor_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The extended dynamic state features. `None` when the struct is not part
+    /// of the p_next chain, e.g. when the device doesn't support the
+    /// VK_EXT_extended_dynamic_state extension.
+    pub fn extended_dynamic_state_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT> {
+        self.extended_dynamic_state_features.as_ref()
+    }
+
+    /// Mutable access to the extended dynamic state features. The struct is
+    /// added to the p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the
+    /// VK_EXT_extended_dynamic_state device extension. The v2 and v3
+    /// additions are separate structs with their own extensions, so each can
+    /// be enabled on its own.
+    pub fn extended_dynamic_state_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT {
+        self.extended_dynamic_state_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The extended dynamic state 2 features. `None` when the struct is not
+    /// part of the p_next chain, e.g. when the device doesn't support the
+    /// VK_EXT_extended_dynamic_state2 extension.
+    pub fn extended_dynamic_state2_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceExtendedDynamicState2FeaturesEXT> {
+        self.extended_dynamic_state2_features.as_ref()
+    }
+
+    /// Mutable access to the extended dynamic state 2 features. The struct is
+    /// added to the p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the
+    /// VK_EXT_extended_dynamic_state2 device extension.
+    pub fn extended_dynamic_state2_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceExtendedDynamicState2FeaturesEXT {
+        self.extended_dynamic_state2_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The extended dynamic state 3 features. `None` when the struct is not
+    /// part of the p_next chain, e.g. when the device doesn't support the
+    /// VK_EXT_extended_dynamic_state3 extension.
+    pub fn extended_dynamic_state3_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceExtendedDynamicState3FeaturesEXT> {
+        self.extended_dynamic_state3_features.as_ref()
+    }
+
+    /// Mutable access to the extended dynamic state 3 features. The struct is
+    /// added to the p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the
+    /// VK_EXT_extended_dynamic_state3 device extension.
+    pub fn extended_dynamic_state3_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceExtendedDynamicState3FeaturesEXT {
+        self.extended_dynamic_state3_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The provoking vertex features. `None` when the struct is not part of the
+    /// p_next chain, e.g. when the device doesn't support the
+    /// VK_EXT_provoking_vertex extension.
+    pub fn provoking_vertex_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceProvokingVertexFeaturesEXT> {
+        self.provoking_vertex_features.as_ref()
+    }
+
+    /// Mutable access to the provoking vertex features. The struct is added to
+    /// the p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the VK_EXT_provoking_vertex
+    /// device extension. `provoking_vertex_last` allows flat-shaded
+    /// attributes to come from the last vertex of each primitive, like
+    /// OpenGL.
+    pub fn provoking_vertex_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceProvokingVertexFeaturesEXT {
+        self.provoking_vertex_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// The depth clip control features. `None` when the struct is not part of
+    /// the p_next chain, e.g. when the device doesn't support the
+    /// VK_EXT_depth_clip_control extension.
+    pub fn depth_clip_control_features(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceDepthClipControlFeaturesEXT> {
+        self.depth_clip_control_features.as_ref()
+    }
+
+    /// Mutable access to the depth clip control features. The struct is added
+    /// to the p_next chain if it wasn't already present.
+    ///
+    /// Enabling any of these features requires the VK_EXT_depth_clip_control
+    /// device extension. `depth_clip_control` allows pipelines to use
+    /// OpenGL's [-1, 1] clip space depth range instead of Vulkan's [0, 1].
+    pub fn depth_clip_control_features_mut(
+        &mut self,
+    ) -> &mut vk::PhysicalDeviceDepthClipControlFeaturesEXT {
+        self.depth_clip_control_features
+            .get_or_insert_with(Default::default)
+    }
+
+    /// Append a user-provided chain of feature structs to the tail of the
+    /// p_next chain.
+    ///
+    /// This is an escape hatch for extension features which aren't modeled
+    /// by this crate. The extra structs are only linked when
+    /// [PhysicalDeviceFeatures::link_p_next_chain] is called, e.g. when
+    /// creating a logical device. They are never queried or checked by
+    /// [PhysicalDeviceFeatures::is_supported_by].
+    ///
+    /// # Params
+    ///
+    /// * `ptr` - the first struct in the extra chain. A null pointer removes
+    ///   any previously set chain.
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must point to a valid Vulkan feature struct whose own p_next
+    ///   chain is valid and null-terminated.
+    /// * The extra chain must outlive every use of this struct, and every
+    ///   copy of it, which calls
+    ///   [PhysicalDeviceFeatures::link_p_next_chain]. Copies share the same
+    ///   pointer rather than the structs behind it.
+    /// * The extra chain must not contain any struct type which this crate
+    ///   already links, and must not be mutated while Vulkan reads it.
+    pub unsafe fn set_extra_p_next(&mut self, ptr: *mut c_void) {
+        self.extra_p_next = NonNull::new(ptr);
+    }
+
+    /// Leave the Vulkan 1.3 features struct out of the p_next chain.
+    ///
+    /// The struct is only valid for Vulkan 1.3 devices and instances. This
+    /// is set automatically by [PhysicalDeviceFeatures::from_physical_device]
+    /// and applied by the logical device for older versions.
+    pub(crate) fn omit_vulkan_13_features(&mut self) {
+        self.omit_vulkan_13_features = true;
+    }
+
     /// Link all of the contained device feature structs using their p_next
     /// pointers.
     ///
@@ -76,10 +817,48 @@ impl PhysicalDeviceFeatures {
             .descriptor_indexing_features
             as *mut vk::PhysicalDeviceDescriptorIndexingFeatures
             as *mut c_void;
-        self.descriptor_indexing_features.p_next = &mut self
-            .physical_device_vulkan_13_features
-            as *mut vk::PhysicalDeviceVulkan13Features
-            as *mut c_void;
+        let mut p_next_tail = &mut self.descriptor_indexing_features.p_next;
+        if !self.omit_vulkan_13_features {
+            *p_next_tail = &mut self.physical_device_vulkan_13_features
+                as *mut vk::PhysicalDeviceVulkan13Features
+                as *mut c_void;
+            p_next_tail = &mut self.physical_device_vulkan_13_features.p_next;
+        }
+
+        // optional structs are appended to the tail of the chain
+        macro_rules! link_optional {
+            ($struct_name:ident) => {
+                if let Some(features) = &mut self.$struct_name {
+                    *p_next_tail = features as *mut _ as *mut c_void;
+                    p_next_tail = &mut features.p_next;
+                }
+            };
+        }
+        link_optional!(storage_8bit_features);
+        link_optional!(storage_16bit_features);
+        link_optional!(robustness2_features);
+        link_optional!(protected_memory_features);
+        link_optional!(host_query_reset_features);
+        link_optional!(conditional_rendering_features);
+        link_optional!(descriptor_buffer_features);
+        link_optional!(fragment_shading_rate_features);
+        link_optional!(multiview_features);
+        link_optional!(shader_draw_parameters_features);
+        link_optional!(shader_object_features);
+        link_optional!(shader_atomic_float_features);
+        link_optional!(cooperative_matrix_features);
+        link_optional!(present_id_features);
+        link_optional!(present_wait_features);
+        link_optional!(vertex_attribute_divisor_features);
+        link_optional!(extended_dynamic_state_features);
+        link_optional!(extended_dynamic_state2_features);
+        link_optional!(extended_dynamic_state3_features);
+        link_optional!(provoking_vertex_features);
+        link_optional!(depth_clip_control_features);
+        *p_next_tail = self
+            .extra_p_next
+            .map_or(std::ptr::null_mut(), NonNull::as_ptr);
+
         &mut self.physical_device_features2
     }
 }
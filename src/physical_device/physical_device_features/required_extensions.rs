@@ -0,0 +1,98 @@
+use {crate::PhysicalDeviceFeatures, ash::vk, std::ffi::CStr};
+
+/// Optional feature structs which can only be enabled along with a device
+/// extension. Structs which appear more than once also need the extension's
+/// prerequisites.
+const EXTENSION_FEATURES: &[(&str, fn() -> &'static CStr)] = &[
+    ("robustness2_features", vk::ExtRobustness2Fn::name),
+    (
+        "conditional_rendering_features",
+        vk::ExtConditionalRenderingFn::name,
+    ),
+    (
+        "descriptor_buffer_features",
+        vk::ExtDescriptorBufferFn::name,
+    ),
+    (
+        "fragment_shading_rate_features",
+        vk::KhrFragmentShadingRateFn::name,
+    ),
+    ("shader_object_features", vk::ExtShaderObjectFn::name),
+    (
+        "shader_atomic_float_features",
+        vk::ExtShaderAtomicFloatFn::name,
+    ),
+    (
+        "cooperative_matrix_features",
+        vk::NvCooperativeMatrixFn::name,
+    ),
+    ("present_id_features", vk::KhrPresentIdFn::name),
+    ("present_id_features", vk::KhrSwapchainFn::name),
+    ("present_wait_features", vk::KhrPresentWaitFn::name),
+    ("present_wait_features", vk::KhrPresentIdFn::name),
+    ("present_wait_features", vk::KhrSwapchainFn::name),
+    (
+        "vertex_attribute_divisor_features",
+        vk::ExtVertexAttributeDivisorFn::name,
+    ),
+    (
+        "extended_dynamic_state_features",
+        vk::ExtExtendedDynamicStateFn::name,
+    ),
+    (
+        "extended_dynamic_state2_features",
+        vk::ExtExtendedDynamicState2Fn::name,
+    ),
+    (
+        "extended_dynamic_state3_features",
+        vk::ExtExtendedDynamicState3Fn::name,
+    ),
+    ("provoking_vertex_features", vk::ExtProvokingVertexFn::name),
+    (
+        "depth_clip_control_features",
+        vk::ExtDepthClipControlFn::name,
+    ),
+];
+
+impl PhysicalDeviceFeatures {
+    /// The device extensions which must be enabled to use the enabled
+    /// features.
+    ///
+    /// Only features which come from a device extension are considered.
+    /// Features which are core in the device's API version need no extension.
+    ///
+    /// # Returns
+    ///
+    /// The extension names, in a stable order and without duplicates.
+    pub fn required_extensions(&self) -> Vec<&'static CStr> {
+        let mut enabled_structs: Vec<&'static str> = vec![];
+        macro_rules! skip_required_feature {
+            ($($struct_path:ident).+, $feature_name:ident) => {};
+        }
+        macro_rules! check_optional_feature {
+            ($struct_name:ident, $feature_name:ident) => {
+                let is_enabled = self
+                    .$struct_name
+                    .map(|features| features.$feature_name == vk::TRUE)
+                    .unwrap_or(false);
+                let struct_name = stringify!($struct_name);
+                if is_enabled && !enabled_structs.contains(&struct_name) {
+                    enabled_structs.push(struct_name);
+                }
+            };
+        }
+
+        for_each_feature!(skip_required_feature, check_optional_feature);
+
+        let mut extensions: Vec<&'static CStr> = vec![];
+        for (struct_name, extension_name) in EXTENSION_FEATURES {
+            let extension_name = extension_name();
+            if enabled_structs.contains(struct_name)
+                && !extensions.contains(&extension_name)
+            {
+                extensions.push(extension_name);
+            }
+        }
+        extensions
+    }
+}
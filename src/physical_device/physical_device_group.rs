@@ -0,0 +1,113 @@
+use {
+    crate::{
+        InstanceResult, PhysicalDevice, PhysicalDeviceFeatures, VulkanHandle,
+        VulkanInstance,
+    },
+    ash::vk,
+};
+
+/// A set of physical devices which can be used together by a single logical
+/// device, e.g. for linked multi-GPU rendering.
+#[derive(Clone, Debug)]
+pub struct PhysicalDeviceGroup {
+    physical_devices: Vec<PhysicalDevice>,
+    subset_allocation: bool,
+}
+
+impl PhysicalDeviceGroup {
+    /// The physical devices in this group.
+    pub fn physical_devices(&self) -> &[PhysicalDevice] {
+        &self.physical_devices
+    }
+
+    /// True when memory allocations can be made on a subset of the devices
+    /// in the group.
+    pub fn subset_allocation(&self) -> bool {
+        self.subset_allocation
+    }
+
+    /// Returns true when the physical device is a member of this group.
+    pub fn contains(&self, physical_device: &PhysicalDevice) -> bool {
+        self.physical_devices.iter().any(|member| unsafe {
+            // SAFE because the handles are only compared.
+            member.raw() == physical_device.raw()
+        })
+    }
+
+    /// Get the raw handles for every device in the group.
+    pub(crate) fn raw_handles(&self) -> Vec<vk::PhysicalDevice> {
+        self.physical_devices
+            .iter()
+            .map(|member| unsafe {
+                // SAFE because the handles are only used while the group's
+                // devices exist.
+                *member.raw()
+            })
+            .collect()
+    }
+}
+
+impl PhysicalDevice {
+    /// Enumerate all physical device groups.
+    ///
+    /// Every physical device is a member of exactly one group. The features
+    /// stored on each device are the features it actually supports and no
+    /// features are requested.
+    ///
+    /// Device groups were added in Vulkan 1.1. Instances which predate it
+    /// get one single-device group for each device from
+    /// [PhysicalDevice::enumerate_all].
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which will provide access to the
+    ///   physical devices.
+    pub fn enumerate_device_groups(
+        instance: &VulkanInstance,
+    ) -> InstanceResult<Vec<PhysicalDeviceGroup>> {
+        if instance.api_version() < vk::API_VERSION_1_1 {
+            return Ok(Self::enumerate_all(instance)?
+                .into_iter()
+                .map(|physical_device| PhysicalDeviceGroup {
+                    physical_devices: vec![physical_device],
+                    subset_allocation: false,
+                })
+                .collect());
+        }
+
+        let group_count =
+            unsafe { instance.ash().enumerate_physical_device_groups_len()? };
+        let mut group_properties =
+            vec![vk::PhysicalDeviceGroupProperties::default(); group_count];
+        unsafe {
+            instance
+                .ash()
+                .enumerate_physical_device_groups(&mut group_properties)?
+        };
+
+        let mut groups = vec![];
+        for properties in group_properties {
+            let mut physical_devices = vec![];
+            for &physical_device in &properties.physical_devices
+                [..properties.physical_device_count as usize]
+            {
+                let available_features =
+                    PhysicalDeviceFeatures::from_physical_device(
+                        instance,
+                        &physical_device,
+                    );
+                physical_devices.push(Self::new(
+                    instance,
+                    physical_device,
+                    available_features,
+                    PhysicalDeviceFeatures::default(),
+                )?);
+            }
+            groups.push(PhysicalDeviceGroup {
+                physical_devices,
+                subset_allocation: properties.subset_allocation == vk::TRUE,
+            });
+        }
+        Ok(groups)
+    }
+}
@@ -0,0 +1,82 @@
+use {
+    crate::{PhysicalDevice, PhysicalDeviceFeatures, PhysicalDeviceProperties},
+    ash::vk,
+};
+
+/// An owned snapshot of everything known about a physical device, without
+/// the Vulkan handle.
+///
+/// The snapshot can be cached or sent to other threads without worrying about
+/// the lifetime of the instance which enumerated the device.
+#[derive(Clone, Debug)]
+pub struct PhysicalDeviceInfo {
+    name: String,
+    device_type: vk::PhysicalDeviceType,
+    properties: PhysicalDeviceProperties,
+    features: PhysicalDeviceFeatures,
+    extension_names: Vec<String>,
+    queue_family_properties: Vec<vk::QueueFamilyProperties>,
+    memory_properties: vk::PhysicalDeviceMemoryProperties,
+}
+
+/// # Safety
+///
+/// Send and Sync must be implemented explicitly for the same reason as
+/// [PhysicalDevice]. The p_next pointers in the properties and features are
+/// only used while linking a chain through a mutable reference.
+unsafe impl Send for PhysicalDeviceInfo {}
+unsafe impl Sync for PhysicalDeviceInfo {}
+
+impl PhysicalDeviceInfo {
+    /// The physical device name from the device properties struct.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The kind of device, e.g. a discrete or integrated GPU.
+    pub fn device_type(&self) -> vk::PhysicalDeviceType {
+        self.device_type
+    }
+
+    /// The properties for the physical device.
+    pub fn properties(&self) -> &PhysicalDeviceProperties {
+        &self.properties
+    }
+
+    /// The features supported by the physical device.
+    pub fn features(&self) -> &PhysicalDeviceFeatures {
+        &self.features
+    }
+
+    /// The names of every extension supported by the physical device.
+    pub fn extension_names(&self) -> &[String] {
+        &self.extension_names
+    }
+
+    /// Properties for all queue families supported by the physical device.
+    pub fn queue_family_properties(&self) -> &[vk::QueueFamilyProperties] {
+        &self.queue_family_properties
+    }
+
+    /// The memory heaps and types supported by the physical device.
+    pub fn memory_properties(&self) -> &vk::PhysicalDeviceMemoryProperties {
+        &self.memory_properties
+    }
+}
+
+impl PhysicalDevice {
+    /// Take an owned snapshot of this device's capabilities which doesn't
+    /// include the Vulkan handle.
+    pub fn info(&self) -> PhysicalDeviceInfo {
+        let properties = *self.properties();
+        PhysicalDeviceInfo {
+            name: self.name(),
+            device_type: properties.properties().device_type,
+            properties,
+            features: *self.features(),
+            extension_names: self.available_extension_names().to_vec(),
+            queue_family_properties: self.queue_family_properties().to_vec(),
+            memory_properties: *self.memory_properties(),
+        }
+    }
+}
@@ -1,20 +1,98 @@
-use {crate::VulkanInstance, ash::vk};
+use {
+    crate::{ffi, VulkanInstance},
+    ash::vk,
+    std::ffi::{c_void, CStr},
+};
 
 /// An owned set of physical device features.
 #[derive(Copy, Clone, Debug, Default)]
 pub struct PhysicalDeviceProperties {
     physical_device_properties: vk::PhysicalDeviceProperties2,
+
+    // Optional property structs are only linked into the p_next chain when
+    // present.
+    descriptor_buffer_properties:
+        Option<vk::PhysicalDeviceDescriptorBufferPropertiesEXT>,
+    push_descriptor_properties:
+        Option<vk::PhysicalDevicePushDescriptorPropertiesKHR>,
+    inline_uniform_block_properties:
+        Option<vk::PhysicalDeviceInlineUniformBlockProperties>,
+    fragment_shading_rate_properties:
+        Option<vk::PhysicalDeviceFragmentShadingRatePropertiesKHR>,
+    vertex_attribute_divisor_properties:
+        Option<vk::PhysicalDeviceVertexAttributeDivisorPropertiesEXT>,
 }
 
 unsafe impl Send for PhysicalDeviceProperties {}
 
 impl PhysicalDeviceProperties {
     /// Get the properties from a physical device.
+    ///
+    /// Optional property structs are only queried when the API version or
+    /// available extensions support them. The API version is the older of
+    /// the device's and the instance's versions. Only the core Vulkan 1.0
+    /// properties are queried when either predates Vulkan 1.1.
     pub fn from_physical_device(
         instance: &VulkanInstance,
         physical_device: &vk::PhysicalDevice,
     ) -> Self {
+        let device_api_version = unsafe {
+            instance
+                .ash()
+                .get_physical_device_properties(*physical_device)
+                .api_version
+        };
+        let api_version = device_api_version.min(instance.api_version());
+
+        // The *2 queries, and every chained struct, need Vulkan 1.1 support
+        // from both the instance and the device.
+        if api_version < vk::API_VERSION_1_1 {
+            let mut properties = Self::default();
+            properties.physical_device_properties.properties = unsafe {
+                instance
+                    .ash()
+                    .get_physical_device_properties(*physical_device)
+            };
+            return properties;
+        }
+
+        let extension_names: Vec<String> = unsafe {
+            instance
+                .ash()
+                .enumerate_device_extension_properties(*physical_device)
+                .unwrap_or_default()
+        }
+        .iter()
+        .map(|props| ffi::string_from_i8(&props.extension_name))
+        .filter_map(|name| name.ok())
+        .collect();
+        let has_extension = |name: &CStr| {
+            extension_names
+                .iter()
+                .any(|available| Some(available.as_str()) == name.to_str().ok())
+        };
+
         let mut properties = Self::default();
+        if api_version >= vk::API_VERSION_1_3
+            || has_extension(vk::ExtInlineUniformBlockFn::name())
+        {
+            properties.inline_uniform_block_properties =
+                Some(Default::default());
+        }
+        if has_extension(vk::ExtDescriptorBufferFn::name()) {
+            properties.descriptor_buffer_properties = Some(Default::default());
+        }
+        if has_extension(vk::KhrPushDescriptorFn::name()) {
+            properties.push_descriptor_properties = Some(Default::default());
+        }
+        if has_extension(vk::KhrFragmentShadingRateFn::name()) {
+            properties.fragment_shading_rate_properties =
+                Some(Default::default());
+        }
+        if has_extension(vk::ExtVertexAttributeDivisorFn::name()) {
+            properties.vertex_attribute_divisor_properties =
+                Some(Default::default());
+        }
         unsafe {
             instance.ash().get_physical_device_properties2(
                 *physical_device,
@@ -32,7 +110,125 @@ impl PhysicalDeviceProperties {
         &mut self.physical_device_properties.properties
     }
 
-    /// Link all of the contained device feature structs using their p_next
+    /// The required alignment, in bytes, for dynamic uniform buffer offsets
+    /// and uniform buffer descriptor ranges.
+    pub fn min_uniform_buffer_offset_alignment(&self) -> vk::DeviceSize {
+        self.properties().limits.min_uniform_buffer_offset_alignment
+    }
+
+    /// The required alignment, in bytes, for dynamic storage buffer offsets
+    /// and storage buffer descriptor ranges.
+    pub fn min_storage_buffer_offset_alignment(&self) -> vk::DeviceSize {
+        self.properties().limits.min_storage_buffer_offset_alignment
+    }
+
+    /// The optimal alignment, in bytes, for buffer offsets in buffer-image
+    /// copies.
+    pub fn optimal_buffer_copy_offset_alignment(&self) -> vk::DeviceSize {
+        self.properties()
+            .limits
+            .optimal_buffer_copy_offset_alignment
+    }
+
+    /// The optimal alignment, in bytes, for the row pitch of buffer-image
+    /// copies.
+    pub fn optimal_buffer_copy_row_pitch_alignment(&self) -> vk::DeviceSize {
+        self.properties()
+            .limits
+            .optimal_buffer_copy_row_pitch_alignment
+    }
+
+    /// The required alignment, in bytes, for the offset and size of
+    /// non-coherent memory flushes and invalidations.
+    pub fn non_coherent_atom_size(&self) -> vk::DeviceSize {
+        self.properties().limits.non_coherent_atom_size
+    }
+
+    /// Decode the packed driver version into a human-readable string.
+    ///
+    /// The encoding is vendor-specific:
+    ///
+    /// * NVIDIA packs the version as 10.8.8.6 bits, e.g. "535.104.5.0".
+    /// * Intel on Windows packs the version as 18.14 bits, e.g.
+    ///   "101.4502".
+    /// * Everything else uses the Vulkan version encoding, e.g. "23.1.3".
+    pub fn driver_version_string(&self) -> String {
+        const NVIDIA_VENDOR_ID: u32 = 0x10DE;
+        const INTEL_VENDOR_ID: u32 = 0x8086;
+
+        let properties = self.properties();
+        let version = properties.driver_version;
+        match properties.vendor_id {
+            NVIDIA_VENDOR_ID => format!(
+                "{}.{}.{}.{}",
+                (version >> 22) & 0x3ff,
+                (version >> 14) & 0xff,
+                (version >> 6) & 0xff,
+                version & 0x3f,
+            ),
+            INTEL_VENDOR_ID if cfg!(target_os = "windows") => {
+                format!("{}.{}", version >> 14, version & 0x3fff)
+            }
+            _ => format!(
+                "{}.{}.{}",
+                vk::api_version_major(version),
+                vk::api_version_minor(version),
+                vk::api_version_patch(version),
+            ),
+        }
+    }
+
+    /// The descriptor buffer sizes and alignments. `None` when the device
+    /// doesn't support the VK_EXT_descriptor_buffer extension.
+    pub fn descriptor_buffer_properties(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceDescriptorBufferPropertiesEXT> {
+        self.descriptor_buffer_properties.as_ref()
+    }
+
+    /// The push descriptor limits, like `max_push_descriptors`. `None` when
+    /// the device doesn't support the VK_KHR_push_descriptor extension.
+    pub fn push_descriptor_properties(
+        &self,
+    ) -> Option<&vk::PhysicalDevicePushDescriptorPropertiesKHR> {
+        self.push_descriptor_properties.as_ref()
+    }
+
+    /// The inline uniform block limits. `None` when the device predates
+    /// Vulkan 1.3 and doesn't support the VK_EXT_inline_uniform_block
+    /// extension.
+    pub fn inline_uniform_block_properties(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceInlineUniformBlockProperties> {
+        self.inline_uniform_block_properties.as_ref()
+    }
+
+    /// The fragment shading rate limits, like the supported attachment texel
+    /// sizes. `None` when the device doesn't support the
+    /// VK_KHR_fragment_shading_rate extension.
+    pub fn fragment_shading_rate_properties(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceFragmentShadingRatePropertiesKHR> {
+        self.fragment_shading_rate_properties.as_ref()
+    }
+
+    /// The vertex attribute divisor limits. `None` when the device doesn't
+    /// support the VK_EXT_vertex_attribute_divisor extension.
+    pub fn vertex_attribute_divisor_properties(
+        &self,
+    ) -> Option<&vk::PhysicalDeviceVertexAttributeDivisorPropertiesEXT> {
+        self.vertex_attribute_divisor_properties.as_ref()
+    }
+
+    /// The largest divisor which can be used for an instance-rate vertex
+    /// attribute. `None` when the device doesn't support the
+    /// VK_EXT_vertex_attribute_divisor extension.
+    pub fn max_vertex_attrib_divisor(&self) -> Option<u32> {
+        self.vertex_attribute_divisor_properties
+            .map(|properties| properties.max_vertex_attrib_divisor)
+    }
+
+    /// Link all of the contained device property structs using their p_next
     /// pointers.
     ///
     /// # Safety
@@ -41,8 +237,23 @@ impl PhysicalDeviceProperties {
     pub unsafe fn link_p_next_chain(
         &mut self,
     ) -> &mut vk::PhysicalDeviceProperties2 {
-        // this library doesn't currently support any other p_next types
-        // so nothing to link up here
+        // optional structs are appended to the tail of the chain
+        let mut p_next_tail = &mut self.physical_device_properties.p_next;
+        macro_rules! link_optional {
+            ($struct_name:ident) => {
+                if let Some(properties) = &mut self.$struct_name {
+                    *p_next_tail = properties as *mut _ as *mut c_void;
+                    p_next_tail = &mut properties.p_next;
+                }
+            };
+        }
+        link_optional!(descriptor_buffer_properties);
+        link_optional!(push_descriptor_properties);
+        link_optional!(inline_uniform_block_properties);
+        link_optional!(fragment_shading_rate_properties);
+        link_optional!(vertex_attribute_divisor_properties);
+        *p_next_tail = std::ptr::null_mut();
+
         &mut self.physical_device_properties
     }
 }
@@ -0,0 +1,79 @@
+use {
+    crate::{PhysicalDevice, VulkanInstance},
+    ash::vk,
+};
+
+impl PhysicalDevice {
+    /// Check whether a queue family can present on this platform before any
+    /// surface exists.
+    ///
+    /// The platform-specific query is chosen based on the compilation target.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical device. The platform's surface extension must be enabled,
+    ///   e.g. VK_KHR_win32_surface on Windows.
+    /// * `queue_family_index` - the queue family to check.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(supported)` when the platform can answer without a surface.
+    ///   Android always reports true because every queue family there must
+    ///   support presentation.
+    /// * `None` when the platform's surface extension isn't enabled, or when
+    ///   the platform has no such query or needs a display connection, like
+    ///   Xlib, XCB, Wayland, and Metal. Use
+    ///   [PhysicalDevice::queue_family_supports_present] once a surface
+    ///   exists.
+    pub fn queue_family_can_present_platform(
+        &self,
+        instance: &VulkanInstance,
+        queue_family_index: u32,
+    ) -> Option<bool> {
+        platform_presentation_support(
+            instance,
+            self.physical_device,
+            queue_family_index,
+        )
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn platform_presentation_support(
+    instance: &VulkanInstance,
+    physical_device: vk::PhysicalDevice,
+    queue_family_index: u32,
+) -> Option<bool> {
+    use ash::extensions::khr::Win32Surface;
+
+    let win32_surface = instance
+        .extension_loader(Win32Surface::name(), Win32Surface::new)
+        .ok()?;
+    let supported = unsafe {
+        // SAFE because the physical device was enumerated by this instance.
+        win32_surface.get_physical_device_win32_presentation_support(
+            physical_device,
+            queue_family_index,
+        )
+    };
+    Some(supported)
+}
+
+#[cfg(target_os = "android")]
+fn platform_presentation_support(
+    _instance: &VulkanInstance,
+    _physical_device: vk::PhysicalDevice,
+    _queue_family_index: u32,
+) -> Option<bool> {
+    Some(true)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "android")))]
+fn platform_presentation_support(
+    _instance: &VulkanInstance,
+    _physical_device: vk::PhysicalDevice,
+    _queue_family_index: u32,
+) -> Option<bool> {
+    None
+}
@@ -0,0 +1,230 @@
+use {
+    crate::{PhysicalDevice, VulkanInstance},
+    ash::vk,
+    std::ffi::c_void,
+};
+
+impl PhysicalDevice {
+    /// Iterate over every queue family which supports all of the requested
+    /// flags.
+    ///
+    /// # Params
+    ///
+    /// * `flags` - the queue flags each family must contain.
+    ///
+    /// # Returns
+    ///
+    /// An iterator of `(queue_family_index, properties)` pairs.
+    pub fn queue_families_with_flags(
+        &self,
+        flags: vk::QueueFlags,
+    ) -> impl Iterator<Item = (u32, &vk::QueueFamilyProperties)> {
+        self.queue_family_properties()
+            .iter()
+            .enumerate()
+            .filter(move |(_, properties)| {
+                properties.queue_flags.contains(flags)
+            })
+            .map(|(queue_family_index, properties)| {
+                (queue_family_index as u32, properties)
+            })
+    }
+
+    /// Find a queue family for asynchronous transfer operations.
+    ///
+    /// Families are considered in the following order:
+    ///
+    /// 1. A dedicated transfer family which supports `TRANSFER` but neither
+    ///    `GRAPHICS` nor `COMPUTE`. These usually map to the device's DMA
+    ///    engines.
+    /// 2. Any family which supports `TRANSFER`.
+    ///
+    /// # Returns
+    ///
+    /// The index of the first family matching the earliest applicable rule,
+    /// or None if no family reports `TRANSFER` support.
+    pub fn find_dedicated_transfer_family(&self) -> Option<u32> {
+        let dedicated =
+            self.queue_family_properties()
+                .iter()
+                .position(|properties| {
+                    properties.queue_flags.contains(vk::QueueFlags::TRANSFER)
+                        && !properties.queue_flags.intersects(
+                            vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE,
+                        )
+                });
+        let any_transfer = || {
+            self.queue_family_properties()
+                .iter()
+                .position(|properties| {
+                    properties.queue_flags.contains(vk::QueueFlags::TRANSFER)
+                })
+        };
+        dedicated
+            .or_else(any_transfer)
+            .map(|queue_family_index| queue_family_index as u32)
+    }
+
+    /// Find a queue family which can bind memory to sparse resources.
+    ///
+    /// # Returns
+    ///
+    /// The index of the first family which supports `SPARSE_BINDING`, or None
+    /// if the device doesn't support sparse binding.
+    pub fn find_sparse_binding_family(&self) -> Option<u32> {
+        self.queue_families_with_flags(vk::QueueFlags::SPARSE_BINDING)
+            .map(|(queue_family_index, _)| queue_family_index)
+            .next()
+    }
+
+    /// Returns true when both indices refer to the same queue family.
+    ///
+    /// Resources used by queues from a single family can use
+    /// `vk::SharingMode::EXCLUSIVE` without ownership transfers.
+    pub fn queue_families_equal(&self, a: u32, b: u32) -> bool {
+        a == b
+    }
+
+    /// The global priorities which queues in a family can be created with.
+    ///
+    /// # Params
+    ///
+    /// * `queue_family_index` - the family to check.
+    ///
+    /// # Returns
+    ///
+    /// None when the device doesn't support the VK_KHR_global_priority or
+    /// VK_EXT_global_priority_query extensions, or when the index is out of
+    /// range.
+    pub fn queue_family_global_priorities(
+        &self,
+        queue_family_index: u32,
+    ) -> Option<&[vk::QueueGlobalPriorityKHR]> {
+        self.queue_family_global_priorities
+            .get(queue_family_index as usize)
+            .map(|properties| {
+                &properties.priorities[..properties.priority_count as usize]
+            })
+    }
+
+    /// Returns true when queues in the family can be created with the global
+    /// priority, e.g. with [crate::QueueFamilyInfo::set_global_priority].
+    ///
+    /// # Params
+    ///
+    /// * `queue_family_index` - the family to check.
+    /// * `priority` - the desired global priority.
+    ///
+    /// # Returns
+    ///
+    /// False when the supported priorities are unknown, see
+    /// [PhysicalDevice::queue_family_global_priorities].
+    pub fn queue_family_supports_global_priority(
+        &self,
+        queue_family_index: u32,
+        priority: vk::QueueGlobalPriorityKHR,
+    ) -> bool {
+        self.queue_family_global_priorities(queue_family_index)
+            .map(|priorities| priorities.contains(&priority))
+            .unwrap_or(false)
+    }
+
+    /// Returns true when queues in the given family can write timestamps.
+    ///
+    /// # Params
+    ///
+    /// * `queue_family_index` - the family to check. Indices which are out of
+    ///   range are reported as unsupported.
+    pub fn queue_family_supports_timestamps(
+        &self,
+        queue_family_index: u32,
+    ) -> bool {
+        self.queue_family_properties()
+            .get(queue_family_index as usize)
+            .map(|properties| properties.timestamp_valid_bits > 0)
+            .unwrap_or(false)
+    }
+
+    /// Query the properties of every queue family.
+    ///
+    /// The Vulkan 1.1 query is used when both the device and the instance
+    /// support it so the global priority properties can be chained. Only the
+    /// core properties are queried otherwise.
+    ///
+    /// # Returns
+    ///
+    /// The properties of each family, and the global priority properties of
+    /// each family. The second list is empty when the device doesn't
+    /// support querying global priorities.
+    pub(super) fn query_queue_families(
+        instance: &VulkanInstance,
+        physical_device: vk::PhysicalDevice,
+        api_version: u32,
+        extension_names: &[String],
+    ) -> (
+        Vec<vk::QueueFamilyProperties>,
+        Vec<vk::QueueFamilyGlobalPriorityPropertiesKHR>,
+    ) {
+        if api_version < vk::API_VERSION_1_1
+            || instance.api_version() < vk::API_VERSION_1_1
+        {
+            let properties = unsafe {
+                instance.ash().get_physical_device_queue_family_properties(
+                    physical_device,
+                )
+            };
+            return (properties, vec![]);
+        }
+
+        let supports_global_priority_query = [
+            vk::KhrGlobalPriorityFn::name(),
+            vk::ExtGlobalPriorityQueryFn::name(),
+        ]
+        .iter()
+        .any(|name| {
+            extension_names
+                .iter()
+                .any(|available| name.to_str() == Ok(available.as_str()))
+        });
+        let family_count = unsafe {
+            instance
+                .ash()
+                .get_physical_device_queue_family_properties2_len(
+                    physical_device,
+                )
+        };
+        let mut global_priorities = if supports_global_priority_query {
+            vec![
+                vk::QueueFamilyGlobalPriorityPropertiesKHR::default();
+                family_count
+            ]
+        } else {
+            vec![]
+        };
+        let mut properties2 =
+            vec![vk::QueueFamilyProperties2::default(); family_count];
+        for (properties, global_priority) in
+            properties2.iter_mut().zip(global_priorities.iter_mut())
+        {
+            properties.p_next = global_priority as *mut _ as *mut c_void;
+        }
+        unsafe {
+            // SAFE because the chained structs are not moved until after the
+            // query.
+            instance.ash().get_physical_device_queue_family_properties2(
+                physical_device,
+                &mut properties2,
+            );
+        }
+
+        // Clear the pointers so no dangling p_next values are kept.
+        for global_priority in &mut global_priorities {
+            global_priority.p_next = std::ptr::null_mut();
+        }
+        let properties = properties2
+            .iter()
+            .map(|properties| properties.queue_family_properties)
+            .collect();
+        (properties, global_priorities)
+    }
+}
@@ -0,0 +1,24 @@
+/// The reason a physical device was rejected while enumerating supported
+/// devices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectReason {
+    /// The device doesn't support the device extensions needed by the
+    /// required features.
+    MissingExtensions(Vec<String>),
+
+    /// The device doesn't support the named features.
+    MissingFeatures(Vec<&'static str>),
+}
+
+impl std::fmt::Display for RejectReason {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingExtensions(extensions) => {
+                write!(formatter, "missing device extensions {:?}", extensions)
+            }
+            Self::MissingFeatures(features) => {
+                write!(formatter, "missing features {:?}", features)
+            }
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use {
+    crate::{InstanceResult, PhysicalDevice},
+    ash::{extensions::khr::Surface, vk},
+};
+
+impl PhysicalDevice {
+    /// Query the formats and color spaces this device supports when
+    /// presenting to a surface.
+    ///
+    /// # Params
+    ///
+    /// * `surface_loader` - the VK_KHR_surface extension loader.
+    /// * `surface` - the surface which will be presented to.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the surface must be a valid handle created with the
+    /// same instance as this physical device.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use ash::vk;
+    /// # use ccthw_ash_instance::{
+    /// #     InstanceResult, PhysicalDevice, VulkanInstance,
+    /// # };
+    /// # unsafe fn query(
+    /// #     instance: &VulkanInstance,
+    /// #     physical_device: &PhysicalDevice,
+    /// #     surface: vk::SurfaceKHR,
+    /// # ) -> InstanceResult<()> {
+    /// let surface_loader = instance.surface_loader()?;
+    /// let formats =
+    ///     physical_device.surface_formats(&surface_loader, surface)?;
+    /// let capabilities =
+    ///     physical_device.surface_capabilities(&surface_loader, surface)?;
+    ///
+    /// let format = formats
+    ///     .iter()
+    ///     .find(|format| format.format == vk::Format::B8G8R8A8_SRGB)
+    ///     .unwrap_or(&formats[0]);
+    ///
+    /// // A max_image_count of 0 means there is no upper limit.
+    /// let mut image_count = capabilities.min_image_count + 1;
+    /// if capabilities.max_image_count > 0 {
+    ///     image_count = image_count.min(capabilities.max_image_count);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub unsafe fn surface_formats(
+        &self,
+        surface_loader: &Surface,
+        surface: vk::SurfaceKHR,
+    ) -> InstanceResult<Vec<vk::SurfaceFormatKHR>> {
+        let formats = surface_loader.get_physical_device_surface_formats(
+            self.physical_device,
+            surface,
+        )?;
+        Ok(formats)
+    }
+
+    /// Query the capabilities of a surface when used with this device, like
+    /// the supported image counts and extents.
+    ///
+    /// # Params
+    ///
+    /// * `surface_loader` - the VK_KHR_surface extension loader.
+    /// * `surface` - the surface which will be presented to.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the surface must be a valid handle created with the
+    /// same instance as this physical device.
+    pub unsafe fn surface_capabilities(
+        &self,
+        surface_loader: &Surface,
+        surface: vk::SurfaceKHR,
+    ) -> InstanceResult<vk::SurfaceCapabilitiesKHR> {
+        let capabilities = surface_loader
+            .get_physical_device_surface_capabilities(
+                self.physical_device,
+                surface,
+            )?;
+        Ok(capabilities)
+    }
+
+    /// Query the present modes this device supports for a surface.
+    ///
+    /// # Params
+    ///
+    /// * `surface_loader` - the VK_KHR_surface extension loader.
+    /// * `surface` - the surface which will be presented to.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the surface must be a valid handle created with the
+    /// same instance as this physical device.
+    pub unsafe fn surface_present_modes(
+        &self,
+        surface_loader: &Surface,
+        surface: vk::SurfaceKHR,
+    ) -> InstanceResult<Vec<vk::PresentModeKHR>> {
+        let present_modes = surface_loader
+            .get_physical_device_surface_present_modes(
+                self.physical_device,
+                surface,
+            )?;
+        Ok(present_modes)
+    }
+
+    /// Pick a present mode for a surface.
+    ///
+    /// # Params
+    ///
+    /// * `surface_loader` - the VK_KHR_surface extension loader.
+    /// * `surface` - the surface which will be presented to.
+    /// * `preferred` - the desired present mode, e.g. MAILBOX.
+    ///
+    /// # Returns
+    ///
+    /// The preferred mode if the surface supports it. Otherwise FIFO, which
+    /// every implementation is required to support.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the surface must be a valid handle created with the
+    /// same instance as this physical device.
+    pub unsafe fn choose_present_mode(
+        &self,
+        surface_loader: &Surface,
+        surface: vk::SurfaceKHR,
+        preferred: vk::PresentModeKHR,
+    ) -> InstanceResult<vk::PresentModeKHR> {
+        let present_modes =
+            self.surface_present_modes(surface_loader, surface)?;
+        if present_modes.contains(&preferred) {
+            Ok(preferred)
+        } else {
+            Ok(vk::PresentModeKHR::FIFO)
+        }
+    }
+
+    /// Returns true when queues in the given family can present to the
+    /// surface.
+    ///
+    /// # Params
+    ///
+    /// * `surface_loader` - the VK_KHR_surface extension loader.
+    /// * `surface` - the surface which will be presented to.
+    /// * `queue_family_index` - the queue family to check.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the surface must be a valid handle created with the
+    /// same instance as this physical device.
+    pub unsafe fn queue_family_supports_present(
+        &self,
+        surface_loader: &Surface,
+        surface: vk::SurfaceKHR,
+        queue_family_index: u32,
+    ) -> InstanceResult<bool> {
+        let supported = surface_loader.get_physical_device_surface_support(
+            self.physical_device,
+            queue_family_index,
+            surface,
+        )?;
+        Ok(supported)
+    }
+
+    /// Find queue families for rendering and presenting to a surface.
+    ///
+    /// A single family which supports both graphics and presentation is
+    /// preferred. Otherwise the first graphics family and the first family
+    /// which can present are used.
+    ///
+    /// # Params
+    ///
+    /// * `surface_loader` - the VK_KHR_surface extension loader.
+    /// * `surface` - the surface which will be presented to.
+    ///
+    /// # Returns
+    ///
+    /// The `(graphics_family_index, present_family_index, same_family)`, or
+    /// None if the device lacks either kind of queue family. `same_family`
+    /// is true when both indices are the same, so swapchain images can use
+    /// `vk::SharingMode::EXCLUSIVE`. Otherwise the images must be shared
+    /// with `vk::SharingMode::CONCURRENT` or explicitly transferred between
+    /// families.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the surface must be a valid handle created with the
+    /// same instance as this physical device.
+    pub unsafe fn find_graphics_and_present_families(
+        &self,
+        surface_loader: &Surface,
+        surface: vk::SurfaceKHR,
+    ) -> InstanceResult<Option<(u32, u32, bool)>> {
+        let mut graphics_family = None;
+        let mut present_family = None;
+        for (index, properties) in
+            self.queue_family_properties().iter().enumerate()
+        {
+            let index = index as u32;
+            let supports_graphics =
+                properties.queue_flags.contains(vk::QueueFlags::GRAPHICS);
+            let supports_present = self.queue_family_supports_present(
+                surface_loader,
+                surface,
+                index,
+            )?;
+            if supports_graphics && supports_present {
+                return Ok(Some((index, index, true)));
+            }
+            if supports_graphics && graphics_family.is_none() {
+                graphics_family = Some(index);
+            }
+            if supports_present && present_family.is_none() {
+                present_family = Some(index);
+            }
+        }
+        Ok(graphics_family.zip(present_family).map(
+            |(graphics_family, present_family)| {
+                (
+                    graphics_family,
+                    present_family,
+                    self.queue_families_equal(graphics_family, present_family),
+                )
+            },
+        ))
+    }
+}
@@ -0,0 +1,51 @@
+use {
+    crate::{ffi, InstanceResult, PhysicalDevice, VulkanInstance},
+    ash::vk,
+};
+
+impl PhysicalDevice {
+    /// Query the tools, like RenderDoc or the validation layers, which are
+    /// currently active for this device.
+    ///
+    /// Applications can use this to disable features which are incompatible
+    /// with a capture tool. The core Vulkan 1.3 query is used when both the
+    /// device and instance support it, otherwise the VK_EXT_tooling_info
+    /// extension is used.
+    ///
+    /// # Params
+    ///
+    /// * `instance` - the Vulkan instance which provides access to the
+    ///   physical device.
+    ///
+    /// # Returns
+    ///
+    /// The active tools, or an empty list when the device supports neither
+    /// Vulkan 1.3 nor the VK_EXT_tooling_info extension.
+    pub fn tool_properties(
+        &self,
+        instance: &VulkanInstance,
+    ) -> InstanceResult<Vec<vk::PhysicalDeviceToolProperties>> {
+        let get_tool_properties = if self.properties().properties().api_version
+            >= vk::API_VERSION_1_3
+            && instance.api_version() >= vk::API_VERSION_1_3
+        {
+            instance.ash().fp_v1_3().get_physical_device_tool_properties
+        } else if self
+            .extension_version(&vk::ExtToolingInfoFn::name().to_string_lossy())
+            .is_some()
+        {
+            instance
+                .load_instance_fns(|f| vk::ExtToolingInfoFn::load(f))
+                .get_physical_device_tool_properties_ext
+        } else {
+            return Ok(vec![]);
+        };
+
+        let tools = unsafe {
+            ffi::enumerate(|count, values| {
+                get_tool_properties(self.physical_device, count, values)
+            })?
+        };
+        Ok(tools)
+    }
+}
@@ -0,0 +1,182 @@
+use {
+    super::debug_callback::DebugCallbackState,
+    crate::{InstanceResult, VulkanInstance},
+    ash::vk,
+    std::path::PathBuf,
+};
+
+/// Configuration for creating a [VulkanInstance].
+///
+/// # Examples
+///
+/// ```
+/// use ccthw_ash_instance::VulkanInstance;
+///
+/// let instance = unsafe { VulkanInstance::builder().build().unwrap() };
+/// ```
+#[derive(Debug, Clone)]
+pub struct VulkanInstanceBuilder {
+    pub(super) required_extensions: Vec<String>,
+    pub(super) required_layers: Vec<String>,
+    pub(super) validation_feature_enables: Vec<vk::ValidationFeatureEnableEXT>,
+    pub(super) engine_name: String,
+    pub(super) engine_version: u32,
+    pub(super) instance_flags: vk::InstanceCreateFlags,
+    pub(super) loader_path: Option<PathBuf>,
+    pub(super) application_info: Option<vk::ApplicationInfo>,
+}
+
+impl Default for VulkanInstanceBuilder {
+    fn default() -> Self {
+        Self {
+            required_extensions: vec![],
+            required_layers: vec![],
+            validation_feature_enables: vec![],
+            engine_name: "no engine".to_owned(),
+            engine_version: vk::make_api_version(0, 1, 0, 0),
+            instance_flags: vk::InstanceCreateFlags::empty(),
+            loader_path: None,
+            application_info: None,
+        }
+    }
+}
+
+impl VulkanInstanceBuilder {
+    /// Create a builder with no required extensions or layers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set all of the extension names required by this application. The
+    /// DebugUtils extension is added automatically when compiled with debug
+    /// assertions or the `debug_utils_always` feature enabled.
+    pub fn required_extensions(mut self, extensions: &[String]) -> Self {
+        self.required_extensions = extensions.to_vec();
+        self
+    }
+
+    /// Set all of the layers required by this application.
+    pub fn required_layers(mut self, layers: &[String]) -> Self {
+        self.required_layers = layers.to_vec();
+        self
+    }
+
+    /// Enable the validation layer's best-practices checks, which flag
+    /// suboptimal-but-legal API usage.
+    ///
+    /// The VK_EXT_validation_features extension is added automatically,
+    /// unless it's already one of the required extensions. That extension is
+    /// provided by the Khronos validation layer, so
+    /// "VK_LAYER_KHRONOS_validation" must also be one of the required layers.
+    /// Otherwise [VulkanInstanceBuilder::build] fails with
+    /// [crate::InstanceError::MissingExtensions].
+    pub fn enable_best_practices(mut self) -> Self {
+        let best_practices = vk::ValidationFeatureEnableEXT::BEST_PRACTICES;
+        if !self.validation_feature_enables.contains(&best_practices) {
+            self.validation_feature_enables.push(best_practices);
+        }
+        self
+    }
+
+    /// Set the engine name and version reported to the driver through
+    /// `vk::ApplicationInfo`.
+    ///
+    /// Defaults to "no engine" version 1.0.0.
+    ///
+    /// # Params
+    ///
+    /// * `name` - the engine's name. Interior nul bytes are not allowed.
+    /// * `version` - the engine's version, typically built with
+    ///   `vk::make_api_version`.
+    pub fn engine(mut self, name: impl Into<String>, version: u32) -> Self {
+        self.engine_name = name.into();
+        self.engine_version = version;
+        self
+    }
+
+    /// Add flags used when creating the instance.
+    ///
+    /// The flags are OR-merged with any flags set by this library and with
+    /// flags from previous calls.
+    pub fn instance_flags(mut self, flags: vk::InstanceCreateFlags) -> Self {
+        self.instance_flags |= flags;
+        self
+    }
+
+    /// Load the Vulkan loader library from an explicit path instead of the
+    /// platform's default search locations.
+    ///
+    /// This is useful for custom Vulkan SDK installs and sandboxed
+    /// environments where the default search fails.
+    ///
+    /// # Params
+    ///
+    /// * `path` - the path to the loader library, e.g.
+    ///   "/opt/vulkan-sdk/lib/libvulkan.so.1".
+    pub fn loader_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.loader_path = Some(path.into());
+        self
+    }
+
+    /// Use a fully-formed application info struct instead of the one built
+    /// by this library.
+    ///
+    /// The struct is used as-is, so the name and version set with
+    /// [VulkanInstanceBuilder::engine] are ignored. The only exception is
+    /// `api_version`: when it's zero, this library requests the same version
+    /// it would otherwise use, see [crate::VulkanInstance::api_version]. A
+    /// non-zero `api_version` always takes precedence.
+    ///
+    /// The pointers in `application_info`, like `p_application_name`, are
+    /// not read until [VulkanInstanceBuilder::build] is called.
+    pub fn application_info(
+        mut self,
+        application_info: vk::ApplicationInfo,
+    ) -> Self {
+        self.application_info = Some(application_info);
+        self
+    }
+
+    /// Create the Vulkan instance.
+    ///
+    /// # Returns
+    ///
+    /// The Vulkan Instance or an InstanceError if any of the extensions or
+    /// layers are unavailable.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because:
+    ///   - The Application must ensure that all device resources created with
+    ///     the instance are destroyed proior to dropping the instance.
+    ///   - The pointers in the application info, if one was provided, must
+    ///     be valid until this call returns.
+    pub unsafe fn build(self) -> InstanceResult<VulkanInstance> {
+        let actual_required_extensions =
+            VulkanInstance::with_additional_extensions(&self);
+
+        let (entry, ash, api_version) = VulkanInstance::create_instance(
+            &self,
+            &actual_required_extensions,
+        )?;
+
+        let mut vulkan_instance = VulkanInstance {
+            layers: self.required_layers,
+            extensions: actual_required_extensions,
+            debug_messenger: None,
+            debug_utils: None,
+            debug_callback_state: Box::new(DebugCallbackState::new()),
+            debug_message_severity:
+                vk::DebugUtilsMessageSeverityFlagsEXT::empty(),
+            debug_message_types: vk::DebugUtilsMessageTypeFlagsEXT::empty(),
+            owns_instance: true,
+            api_version,
+            entry,
+            ash,
+        };
+
+        vulkan_instance.setup_debug_logger()?;
+
+        Ok(vulkan_instance)
+    }
+}
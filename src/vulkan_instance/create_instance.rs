@@ -1,4 +1,5 @@
 use {
+    super::VulkanInstanceBuilder,
     crate::{ffi, InstanceError, InstanceResult, VulkanInstance},
     ash::{extensions::ext::DebugUtils, vk},
     std::ffi::CString,
@@ -6,24 +7,49 @@ use {
 
 impl VulkanInstance {
     pub(super) fn with_additional_extensions(
-        required_extensions: &[String],
+        builder: &VulkanInstanceBuilder,
     ) -> Vec<String> {
-        let mut required_extensions_with_debug = required_extensions.to_vec();
-        if cfg!(debug_assertions) {
+        let mut required_extensions_with_debug =
+            builder.required_extensions.clone();
+        if cfg!(any(debug_assertions, feature = "debug_utils_always")) {
             required_extensions_with_debug
                 .push(DebugUtils::name().to_str().unwrap().to_owned());
         }
+        let validation_features_name =
+            vk::ExtValidationFeaturesFn::name().to_str().unwrap();
+        if !builder.validation_feature_enables.is_empty()
+            && !required_extensions_with_debug
+                .iter()
+                .any(|name| name == validation_features_name)
+        {
+            required_extensions_with_debug
+                .push(validation_features_name.to_owned());
+        }
         required_extensions_with_debug
     }
 
     /// Create the Ash loader and instance.
-    pub(super) fn create_instance(
+    ///
+    /// # Returns
+    ///
+    /// The entry, the instance, and the API version the instance was created
+    /// with.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because any pointers in the builder's application info must be
+    /// valid.
+    pub(super) unsafe fn create_instance(
+        builder: &VulkanInstanceBuilder,
         required_extensions: &[String],
-        required_layers: &[String],
-    ) -> InstanceResult<(ash::Entry, ash::Instance)> {
-        let entry = unsafe { ash::Entry::load()? };
+    ) -> InstanceResult<(ash::Entry, ash::Instance, u32)> {
+        let required_layers = &builder.required_layers;
+        let entry = match &builder.loader_path {
+            Some(path) => unsafe { ash::Entry::load_from(path)? },
+            None => unsafe { ash::Entry::load()? },
+        };
 
-        Self::check_extensions(&entry, required_extensions)?;
+        Self::check_extensions(&entry, required_extensions, required_layers)?;
         Self::check_layers(&entry, required_layers)?;
 
         let (_layer_names, layer_ptrs) =
@@ -32,17 +58,40 @@ impl VulkanInstance {
             unsafe { ffi::to_os_ptrs(required_extensions) };
 
         let app_name = CString::new("ash starter").unwrap();
-        let engine_name = CString::new("no engine").unwrap();
-
-        let app_info = vk::ApplicationInfo {
-            p_engine_name: engine_name.as_ptr(),
-            p_application_name: app_name.as_ptr(),
-            application_version: vk::make_api_version(0, 1, 0, 0),
-            engine_version: vk::make_api_version(0, 1, 0, 0),
-            api_version: vk::make_api_version(0, 1, 3, 0),
+        let engine_name =
+            CString::new(builder.engine_name.as_str()).map_err(|_| {
+                InstanceError::InvalidName(builder.engine_name.clone())
+            })?;
+
+        let mut app_info =
+            builder.application_info.unwrap_or(vk::ApplicationInfo {
+                p_engine_name: engine_name.as_ptr(),
+                p_application_name: app_name.as_ptr(),
+                application_version: vk::make_api_version(0, 1, 0, 0),
+                engine_version: builder.engine_version,
+                ..Default::default()
+            });
+        if app_info.api_version == 0 {
+            app_info.api_version = Self::supported_api_version(&entry);
+        }
+        let validation_features = vk::ValidationFeaturesEXT {
+            enabled_validation_feature_count: builder
+                .validation_feature_enables
+                .len() as u32,
+            p_enabled_validation_features: builder
+                .validation_feature_enables
+                .as_ptr(),
             ..Default::default()
         };
+        let p_next = if builder.validation_feature_enables.is_empty() {
+            std::ptr::null()
+        } else {
+            &validation_features as *const vk::ValidationFeaturesEXT
+                as *const std::ffi::c_void
+        };
         let create_info = vk::InstanceCreateInfo {
+            p_next,
+            flags: builder.instance_flags,
             p_application_info: &app_info,
             pp_enabled_layer_names: layer_ptrs.as_ptr(),
             enabled_layer_count: layer_ptrs.len() as u32,
@@ -52,7 +101,7 @@ impl VulkanInstance {
         };
         let instance = unsafe { entry.create_instance(&create_info, None)? };
 
-        Ok((entry, instance))
+        Ok((entry, instance, app_info.api_version))
     }
 
     /// Check that all required extensions are available.
@@ -62,6 +111,9 @@ impl VulkanInstance {
     /// * `entry` - the Ash library entry
     /// * `required_extensions` - all of the extensions required by the
     ///   application
+    /// * `required_layers` - all of the layers required by the application.
+    ///   Extensions provided by these layers, like VK_EXT_validation_features,
+    ///   are considered available.
     ///
     /// # Returns
     ///
@@ -69,15 +121,12 @@ impl VulkanInstance {
     fn check_extensions(
         entry: &ash::Entry,
         required_extensions: &[String],
+        required_layers: &[String],
     ) -> InstanceResult<()> {
-        let available_names: Vec<String> = entry
-            .enumerate_instance_extension_properties(None)?
-            .iter()
-            .map(|ext| ffi::string_from_i8(&ext.extension_name))
-            .filter_map(|item| item.ok())
-            .collect();
+        let available_names =
+            Self::available_extension_names(entry, required_layers)?;
 
-        log::debug!("Available Vulkan extensions: {:?}", &available_names);
+        log_debug!("Available Vulkan extensions: {:?}", &available_names);
 
         let missing_extensions: Vec<String> = required_extensions
             .iter()
@@ -92,6 +141,62 @@ impl VulkanInstance {
         }
     }
 
+    /// The Vulkan version requested when creating the instance.
+    ///
+    /// This is Vulkan 1.3, or the loader's version when the loader is older.
+    /// A Vulkan 1.0 loader rejects instances which request any later version.
+    pub(super) fn supported_api_version(entry: &ash::Entry) -> u32 {
+        let loader_version = entry
+            .try_enumerate_instance_version()
+            .ok()
+            .flatten()
+            .unwrap_or(vk::API_VERSION_1_0);
+        loader_version.min(vk::API_VERSION_1_3)
+    }
+
+    /// Returns true when an instance extension is available.
+    ///
+    /// This can be used to decide whether to request an optional extension
+    /// before creating the instance.
+    ///
+    /// # Params
+    ///
+    /// * `entry` - the Ash library entry
+    /// * `name` - the extension name, e.g. "VK_KHR_surface"
+    pub fn is_extension_available(
+        entry: &ash::Entry,
+        name: &str,
+    ) -> InstanceResult<bool> {
+        let available_names = Self::available_extension_names(entry, &[])?;
+        Ok(available_names.iter().any(|available| available == name))
+    }
+
+    /// Get the names of every instance extension provided by the
+    /// implementation or by the given layers.
+    fn available_extension_names(
+        entry: &ash::Entry,
+        layers: &[String],
+    ) -> InstanceResult<Vec<String>> {
+        let mut extension_properties =
+            entry.enumerate_instance_extension_properties(None)?;
+        for layer in layers {
+            let Ok(layer_name) = CString::new(layer.as_str()) else {
+                continue;
+            };
+            // Missing layers are reported by check_layers.
+            if let Ok(layer_extensions) =
+                entry.enumerate_instance_extension_properties(Some(&layer_name))
+            {
+                extension_properties.extend(layer_extensions);
+            }
+        }
+        Ok(extension_properties
+            .iter()
+            .map(|ext| ffi::string_from_i8(&ext.extension_name))
+            .filter_map(|item| item.ok())
+            .collect())
+    }
+
     /// Check that all requried layers are available.
     ///
     /// # Params
@@ -113,7 +218,7 @@ impl VulkanInstance {
             .filter_map(|item| item.ok())
             .collect();
 
-        log::debug!("Available Vulkan layers: {:?}", &available_names);
+        log_debug!("Available Vulkan layers: {:?}", &available_names);
 
         let missing: Vec<String> = required_layers
             .iter()
@@ -1,5 +1,9 @@
 use {
-    crate::{InstanceResult, VulkanInstance},
+    super::validation_sink::{
+        DebugMessageFormat, LogValidationSink, ValidationMessage,
+        ValidationObject, ValidationSink,
+    },
+    crate::{InstanceError, InstanceResult, VulkanInstance},
     ash::{
         extensions::ext::DebugUtils,
         vk::{
@@ -7,48 +11,197 @@ use {
             DebugUtilsMessageTypeFlagsEXT, DebugUtilsMessengerCallbackDataEXT,
         },
     },
-    std::{borrow::Cow, ffi::CStr},
+    std::{
+        borrow::Cow,
+        ffi::CStr,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, RwLock,
+        },
+    },
 };
 
 impl VulkanInstance {
     /// Setup debug logging.
     ///
-    /// This is a no-op if the debug_asserts are not enabled.
+    /// This is a no-op if neither debug assertions nor the
+    /// `debug_utils_always` feature are enabled.
     pub(super) fn setup_debug_logger(&mut self) -> InstanceResult<()> {
-        if !cfg!(debug_assertions) {
+        if !cfg!(any(debug_assertions, feature = "debug_utils_always")) {
             return Ok(());
         }
 
-        let debug_utils = DebugUtils::new(self.entry(), self.ash());
-
-        let create_info = vk::DebugUtilsMessengerCreateInfoEXT {
-            message_severity: vk::DebugUtilsMessageSeverityFlagsEXT::VERBOSE
+        self.debug_utils = Some(DebugUtils::new(self.entry(), self.ash()));
+        self.create_debug_messenger(
+            vk::DebugUtilsMessageSeverityFlagsEXT::VERBOSE
                 | vk::DebugUtilsMessageSeverityFlagsEXT::INFO
                 | vk::DebugUtilsMessageSeverityFlagsEXT::WARNING
                 | vk::DebugUtilsMessageSeverityFlagsEXT::ERROR,
-            message_type: vk::DebugUtilsMessageTypeFlagsEXT::GENERAL
+            vk::DebugUtilsMessageTypeFlagsEXT::GENERAL
                 | vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION
                 | vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE,
+        )
+    }
+
+    /// Replace the debug messenger with one which reports the given message
+    /// severities and types.
+    ///
+    /// # Params
+    ///
+    /// * `message_severity` - the message severities to report.
+    /// * `message_type` - the message types to report.
+    ///
+    /// # Returns
+    ///
+    /// Returns [InstanceError::DebugUtilsNotLoaded] if the DebugUtils
+    /// extension isn't loaded, e.g. in release builds.
+    pub fn recreate_debug_messenger(
+        &mut self,
+        message_severity: vk::DebugUtilsMessageSeverityFlagsEXT,
+        message_type: vk::DebugUtilsMessageTypeFlagsEXT,
+    ) -> InstanceResult<()> {
+        let debug_utils = self
+            .debug_utils
+            .as_ref()
+            .ok_or(InstanceError::DebugUtilsNotLoaded)?;
+        if let Some(debug_messenger) = self.debug_messenger.take() {
+            unsafe {
+                // SAFE because the messenger was created by this instance and
+                // is replaced below.
+                debug_utils.destroy_debug_utils_messenger(debug_messenger, None)
+            };
+        }
+        self.create_debug_messenger(message_severity, message_type)
+    }
+
+    /// Replace the debug messenger with one which only reports the given
+    /// message types.
+    ///
+    /// The reported message severities are unchanged. This is useful for
+    /// muting noisy categories, like PERFORMANCE, while keeping VALIDATION
+    /// messages.
+    ///
+    /// # Params
+    ///
+    /// * `message_type` - the message types to report.
+    ///
+    /// # Returns
+    ///
+    /// Returns [InstanceError::DebugUtilsNotLoaded] if the DebugUtils
+    /// extension isn't loaded, e.g. in release builds.
+    pub fn set_debug_message_types(
+        &mut self,
+        message_type: vk::DebugUtilsMessageTypeFlagsEXT,
+    ) -> InstanceResult<()> {
+        self.recreate_debug_messenger(self.debug_message_severity, message_type)
+    }
+
+    /// The message types reported by the debug messenger. This is empty when
+    /// the DebugUtils extension isn't loaded.
+    pub fn debug_message_types(&self) -> vk::DebugUtilsMessageTypeFlagsEXT {
+        self.debug_message_types
+    }
+
+    /// Create a debug messenger which forwards messages to the callback
+    /// state.
+    ///
+    /// The DebugUtils loader must already be set.
+    fn create_debug_messenger(
+        &mut self,
+        message_severity: vk::DebugUtilsMessageSeverityFlagsEXT,
+        message_type: vk::DebugUtilsMessageTypeFlagsEXT,
+    ) -> InstanceResult<()> {
+        let debug_utils = self
+            .debug_utils
+            .as_ref()
+            .ok_or(InstanceError::DebugUtilsNotLoaded)?;
+
+        let create_info = vk::DebugUtilsMessengerCreateInfoEXT {
+            message_severity,
+            message_type,
             pfn_user_callback: Some(debug_callback),
+            p_user_data: &*self.debug_callback_state
+                as *const DebugCallbackState
+                as *mut std::ffi::c_void,
             ..Default::default()
         };
 
         let debug_messenger = unsafe {
             debug_utils.create_debug_utils_messenger(&create_info, None)?
         };
-
-        self.debug_utils = Some(debug_utils);
         self.debug_messenger = Some(debug_messenger);
+        self.debug_message_severity = message_severity;
+        self.debug_message_types = message_type;
 
         Ok(())
     }
 }
 
+/// State shared with the DebugUtils messenger callback through the
+/// messenger's user data pointer.
+pub(super) struct DebugCallbackState {
+    sink: RwLock<Box<dyn ValidationSink>>,
+    format: RwLock<DebugMessageFormat>,
+    error_count: Arc<AtomicUsize>,
+}
+
+impl DebugCallbackState {
+    pub(super) fn new() -> Self {
+        Self {
+            sink: RwLock::new(Box::new(LogValidationSink)),
+            format: RwLock::new(DebugMessageFormat::default()),
+            error_count: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// The shared count of ERROR-severity messages received so far.
+    pub(super) fn error_count(&self) -> &Arc<AtomicUsize> {
+        &self.error_count
+    }
+
+    /// Replace the sink which receives all future messages.
+    pub(super) fn set_sink(&self, sink: Box<dyn ValidationSink>) {
+        let mut current = self
+            .sink
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *current = sink;
+    }
+
+    /// Set the format used for all future messages.
+    pub(super) fn set_format(&self, format: DebugMessageFormat) {
+        let mut current = self
+            .format
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *current = format;
+    }
+
+    /// The format used for messages.
+    pub(super) fn format(&self) -> DebugMessageFormat {
+        *self
+            .format
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn dispatch(&self, message: &ValidationMessage) {
+        if message.severity == DebugUtilsMessageSeverityFlagsEXT::ERROR {
+            self.error_count.fetch_add(1, Ordering::SeqCst);
+        }
+        let sink = self
+            .sink
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        sink.on_message(message);
+    }
+}
+
 unsafe extern "system" fn debug_callback(
     message_severity: DebugUtilsMessageSeverityFlagsEXT,
     message_type: DebugUtilsMessageTypeFlagsEXT,
     p_callback_data: *const DebugUtilsMessengerCallbackDataEXT,
-    _user_data: *mut std::ffi::c_void,
+    p_user_data: *mut std::ffi::c_void,
 ) -> vk::Bool32 {
     let callback_data = *p_callback_data;
 
@@ -69,38 +222,50 @@ unsafe extern "system" fn debug_callback(
         return vk::FALSE;
     }
 
-    let raw_message = std::format!(
-        "VULKAN DEBUG CALLBACK - {:?}::{:?} - [{} ({})]\n\n{}",
-        message_severity,
-        message_type,
-        message_id_name,
-        message_number,
-        message
-    );
-
-    let full_message = raw_message.replace("; ", ";\n\n");
-
-    match message_severity {
-        DebugUtilsMessageSeverityFlagsEXT::VERBOSE => {
-            log::trace!("{}", full_message);
-        }
-
-        DebugUtilsMessageSeverityFlagsEXT::INFO => {
-            log::trace!("{}", full_message);
-        }
-
-        DebugUtilsMessageSeverityFlagsEXT::WARNING => {
-            log::warn!("{}", full_message);
-        }
-
-        DebugUtilsMessageSeverityFlagsEXT::ERROR => {
-            log::error!("{}", full_message);
-        }
+    let raw_objects: &[vk::DebugUtilsObjectNameInfoEXT] =
+        if callback_data.p_objects.is_null() {
+            &[]
+        } else {
+            std::slice::from_raw_parts(
+                callback_data.p_objects,
+                callback_data.object_count as usize,
+            )
+        };
+    let object_names: Vec<Option<Cow<str>>> = raw_objects
+        .iter()
+        .map(|object| {
+            if object.p_object_name.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(object.p_object_name).to_string_lossy())
+            }
+        })
+        .collect();
+    let objects: Vec<ValidationObject> = raw_objects
+        .iter()
+        .zip(&object_names)
+        .map(|(object, name)| ValidationObject {
+            object_type: object.object_type,
+            object_handle: object.object_handle,
+            object_name: name.as_deref(),
+        })
+        .collect();
 
-        _ => {
-            log::warn!("?? {}", full_message);
-        }
-    }
+    // All output goes through the installed sink. Nothing is logged here so
+    // custom sinks have full control over where messages are written.
+    //
+    // SAFE because the user data pointer refers to the callback state owned
+    // by the VulkanInstance, which outlives the messenger.
+    let state = &*(p_user_data as *const DebugCallbackState);
+    state.dispatch(&ValidationMessage {
+        severity: message_severity,
+        message_type,
+        message_id_name: &message_id_name,
+        message_id_number: message_number,
+        message: &message,
+        objects: &objects,
+        format: state.format(),
+    });
 
     vk::FALSE
 }
@@ -0,0 +1,164 @@
+use {crate::VulkanInstance, ash::vk};
+
+impl VulkanInstance {
+    /// Insert a single debug label into a queue, e.g. to mark a frame
+    /// boundary in a RenderDoc or Nsight capture.
+    ///
+    /// This is a no-op for release builds without the `debug_utils_always`
+    /// feature.
+    ///
+    /// # Params
+    ///
+    /// * `queue` - the queue to label.
+    /// * `name` - the label's text.
+    /// * `color` - an RGBA color which tools can use to display the label.
+    ///   All zeros means no color.
+    #[cfg(any(debug_assertions, feature = "debug_utils_always"))]
+    pub fn queue_insert_label(
+        &self,
+        queue: vk::Queue,
+        name: &str,
+        color: [f32; 4],
+    ) {
+        self.with_label(name, color, |debug_utils, label| unsafe {
+            debug_utils.queue_insert_debug_utils_label(queue, label)
+        });
+    }
+
+    /// Insert a single debug label into a queue.
+    ///
+    /// This is a no-op for release builds without the `debug_utils_always`
+    /// feature.
+    #[cfg(not(any(debug_assertions, feature = "debug_utils_always")))]
+    pub fn queue_insert_label(
+        &self,
+        _queue: vk::Queue,
+        _name: &str,
+        _color: [f32; 4],
+    ) {
+        // no-op
+    }
+
+    /// Begin a labeled region of commands in a command buffer.
+    ///
+    /// Every region which was begun must be matched by a call to
+    /// [VulkanInstance::cmd_end_label] in the same command buffer. This is a
+    /// no-op for release builds without the `debug_utils_always` feature.
+    ///
+    /// # Params
+    ///
+    /// * `command_buffer` - the command buffer being recorded.
+    /// * `name` - the region's label text.
+    /// * `color` - an RGBA color which tools can use to display the region.
+    ///   All zeros means no color.
+    ///
+    /// # Returns
+    ///
+    /// True when a region was begun. Nothing is recorded when DebugUtils
+    /// isn't loaded or the name contains a nul byte, and the caller must not
+    /// call [VulkanInstance::cmd_end_label] for it.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the command buffer must be in the recording state.
+    #[cfg(any(debug_assertions, feature = "debug_utils_always"))]
+    pub unsafe fn cmd_begin_label(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        name: &str,
+        color: [f32; 4],
+    ) -> bool {
+        self.with_label(name, color, |debug_utils, label| unsafe {
+            debug_utils.cmd_begin_debug_utils_label(command_buffer, label)
+        })
+    }
+
+    /// Begin a labeled region of commands in a command buffer.
+    ///
+    /// This is a no-op for release builds without the `debug_utils_always`
+    /// feature, so it always returns false.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the command buffer must be in the recording state.
+    #[cfg(not(any(debug_assertions, feature = "debug_utils_always")))]
+    pub unsafe fn cmd_begin_label(
+        &self,
+        _command_buffer: vk::CommandBuffer,
+        _name: &str,
+        _color: [f32; 4],
+    ) -> bool {
+        false
+    }
+
+    /// End the most recent labeled region started with
+    /// [VulkanInstance::cmd_begin_label].
+    ///
+    /// This is a no-op for release builds without the `debug_utils_always`
+    /// feature.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the command buffer must be in the recording state and
+    /// must have an open labeled region, i.e. a call to
+    /// [VulkanInstance::cmd_begin_label] which returned true.
+    #[cfg(any(debug_assertions, feature = "debug_utils_always"))]
+    pub unsafe fn cmd_end_label(&self, command_buffer: vk::CommandBuffer) {
+        if let Some(debug_utils) = &self.debug_utils {
+            debug_utils.cmd_end_debug_utils_label(command_buffer);
+        }
+    }
+
+    /// End the most recent labeled region started with
+    /// [VulkanInstance::cmd_begin_label].
+    ///
+    /// This is a no-op for release builds without the `debug_utils_always`
+    /// feature.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the command buffer must be in the recording state and
+    /// must have an open labeled region.
+    #[cfg(not(any(debug_assertions, feature = "debug_utils_always")))]
+    pub unsafe fn cmd_end_label(&self, _command_buffer: vk::CommandBuffer) {
+        // no-op
+    }
+
+    /// Build a label struct and pass it to `record` if DebugUtils is loaded.
+    ///
+    /// Names which contain a nul byte are logged and skipped. Returns true
+    /// when `record` was called.
+    #[cfg(any(debug_assertions, feature = "debug_utils_always"))]
+    fn with_label(
+        &self,
+        name: &str,
+        color: [f32; 4],
+        record: impl FnOnce(
+            &ash::extensions::ext::DebugUtils,
+            &vk::DebugUtilsLabelEXT,
+        ),
+    ) -> bool {
+        let debug_utils = match self.debug_utils.as_ref() {
+            Some(debug_utils) => debug_utils,
+            None => return false,
+        };
+        let label_name = match std::ffi::CString::new(name) {
+            Ok(label_name) => label_name,
+            Err(error) => {
+                log_warn!(
+                    "Unable to use {:?} as a debug label! {:?}",
+                    name,
+                    error
+                );
+                return false;
+            }
+        };
+        let label = vk::DebugUtilsLabelEXT {
+            p_label_name: label_name.as_ptr(),
+            color,
+            ..Default::default()
+        };
+        record(debug_utils, &label);
+        true
+    }
+}
@@ -0,0 +1,69 @@
+use {crate::VulkanInstance, ash::vk};
+
+/// Ends a labeled region of commands when dropped.
+///
+/// Created by [VulkanInstance::debug_scope]. The region is closed even when
+/// recording returns early, e.g. with `?`. Like the other label helpers this
+/// is a no-op for release builds without the `debug_utils_always` feature.
+/// Nothing is ended if the region was never begun, e.g. because the name
+/// contained a nul byte.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use {ash::vk, ccthw_ash_instance::VulkanInstance};
+/// # fn record(instance: &VulkanInstance, command_buffer: vk::CommandBuffer) {
+/// let red = [1.0, 0.0, 0.0, 1.0];
+/// let _scope =
+///     unsafe { instance.debug_scope(command_buffer, "shadow pass", red) };
+/// // ... record the shadow pass ...
+/// # }
+/// ```
+#[must_use = "the labeled region ends as soon as the scope is dropped"]
+pub struct DebugScope<'a> {
+    instance: &'a VulkanInstance,
+    command_buffer: vk::CommandBuffer,
+    began: bool,
+}
+
+impl Drop for DebugScope<'_> {
+    fn drop(&mut self) {
+        if !self.began {
+            return;
+        }
+        // Safe because debug_scope requires the command buffer to stay in
+        // the recording state for the lifetime of the guard.
+        unsafe { self.instance.cmd_end_label(self.command_buffer) };
+    }
+}
+
+impl VulkanInstance {
+    /// Begin a labeled region of commands which ends when the returned guard
+    /// is dropped.
+    ///
+    /// # Params
+    ///
+    /// * `command_buffer` - the command buffer being recorded.
+    /// * `name` - the region's label text.
+    /// * `color` - an RGBA color which tools can use to display the region.
+    ///   All zeros means no color.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the command buffer must be in the recording state
+    /// when this is called and must still be recording when the guard is
+    /// dropped. The guard must not outlive the command buffer.
+    pub unsafe fn debug_scope(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        name: &str,
+        color: [f32; 4],
+    ) -> DebugScope<'_> {
+        let began = self.cmd_begin_label(command_buffer, name, color);
+        DebugScope {
+            instance: self,
+            command_buffer,
+            began,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use {
+    crate::{InstanceError, InstanceResult, VulkanInstance},
+    ash::extensions::{ext::DebugUtils, khr::Surface},
+    std::ffi::{c_void, CStr},
+};
+
+impl VulkanInstance {
+    /// Create the VK_KHR_surface extension loader for this instance.
+    ///
+    /// # Returns
+    ///
+    /// Returns MissingExtensions if the extension wasn't enabled when
+    /// creating the instance.
+    pub fn surface_loader(&self) -> InstanceResult<Surface> {
+        self.extension_loader(Surface::name(), Surface::new)
+    }
+
+    /// Create any instance extension loader which is built from the entry
+    /// and instance.
+    ///
+    /// # Params
+    ///
+    /// * `name` - the extension's name, e.g. `Surface::name()`.
+    /// * `new` - the loader's constructor, e.g. `Surface::new`.
+    ///
+    /// # Returns
+    ///
+    /// Returns MissingExtensions if the extension wasn't enabled when
+    /// creating the instance.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use ash::extensions::khr::Surface;
+    /// # use ccthw_ash_instance::VulkanInstance;
+    /// # fn loader(instance: &VulkanInstance) {
+    /// let surface = instance.extension_loader(Surface::name(), Surface::new);
+    /// # }
+    /// ```
+    pub fn extension_loader<T>(
+        &self,
+        name: &CStr,
+        new: impl FnOnce(&ash::Entry, &ash::Instance) -> T,
+    ) -> InstanceResult<T> {
+        let name = name.to_string_lossy();
+        if self.extensions().iter().any(|enabled| *enabled == name) {
+            Ok(new(self.entry(), self.ash()))
+        } else {
+            Err(InstanceError::MissingExtensions(vec![name.to_string()]))
+        }
+    }
+
+    /// Load a table of instance-level function pointers, like
+    /// `vk::KhrFragmentShadingRateFn`.
+    ///
+    /// Some extensions add physical device queries, which are instance-level
+    /// functions, to an otherwise device-level extension. Ash has no loader
+    /// for those, so the table is loaded directly from the instance.
+    ///
+    /// # Params
+    ///
+    /// * `load` - the table's constructor, e.g.
+    ///   `|f| vk::KhrFragmentShadingRateFn::load(f)`.
+    pub(crate) fn load_instance_fns<T>(
+        &self,
+        load: impl FnOnce(&mut dyn FnMut(&CStr) -> *const c_void) -> T,
+    ) -> T {
+        load(&mut |name: &CStr| -> *const c_void {
+            unsafe {
+                // SAFE because a missing function is returned as a null
+                // pointer and the instance handle is valid for the lifetime
+                // of self.
+                std::mem::transmute(
+                    self.entry().get_instance_proc_addr(
+                        self.ash().handle(),
+                        name.as_ptr(),
+                    ),
+                )
+            }
+        })
+    }
+
+    /// Borrow the VK_EXT_debug_utils loader used by this instance.
+    ///
+    /// This is `None` when the extension isn't loaded, e.g. in release builds
+    /// without the `debug_utils_always` feature.
+    pub fn debug_utils(&self) -> Option<&DebugUtils> {
+        self.debug_utils.as_ref()
+    }
+}
@@ -1,11 +1,28 @@
 use {
-    crate::error::InstanceResult,
+    crate::{error::InstanceResult, ffi, PhysicalDeviceFeatures, VulkanHandle},
     ash::{extensions::ext::DebugUtils, vk},
-    std::fmt::Debug,
+    std::{ffi::CStr, fmt::Debug, path::Path},
 };
 
+mod builder;
 mod create_instance;
 mod debug_callback;
+mod debug_labels;
+mod debug_scope;
+mod extension_loaders;
+mod validation_error_guard;
+mod validation_sink;
+
+use self::debug_callback::DebugCallbackState;
+pub use self::{
+    builder::VulkanInstanceBuilder,
+    debug_scope::DebugScope,
+    validation_error_guard::ValidationErrorGuard,
+    validation_sink::{
+        DebugMessageFormat, DiscardValidationSink, LogValidationSink,
+        ValidationMessage, ValidationObject, ValidationSink,
+    },
+};
 
 /// The Ash instance, entry, and additional data provided when the instance was
 /// created.
@@ -15,7 +32,15 @@ pub struct VulkanInstance {
 
     debug_messenger: Option<vk::DebugUtilsMessengerEXT>,
     debug_utils: Option<DebugUtils>,
+    debug_callback_state: Box<DebugCallbackState>,
+    debug_message_severity: vk::DebugUtilsMessageSeverityFlagsEXT,
+    debug_message_types: vk::DebugUtilsMessageTypeFlagsEXT,
 
+    // False when the instance was created by someone else and must not be
+    // destroyed when this struct is dropped.
+    owns_instance: bool,
+
+    api_version: u32,
     entry: ash::Entry,
     ash: ash::Instance,
 }
@@ -27,7 +52,8 @@ impl VulkanInstance {
     ///
     /// * `required_extensions` - All of the extension names required by this
     ///   application. The DebugUtils extension is added automatically when
-    ///   compiled with debug assertions enabled.
+    ///   compiled with debug assertions or the `debug_utils_always` feature
+    ///   enabled.
     /// * `required_layers` - All of the layers required by this application.
     ///
     /// # Returns
@@ -44,26 +70,296 @@ impl VulkanInstance {
         required_extensions: &[String],
         required_layers: &[String],
     ) -> InstanceResult<Self> {
-        let actual_required_extensions =
-            Self::with_additional_extensions(required_extensions);
+        Self::builder()
+            .required_extensions(required_extensions)
+            .required_layers(required_layers)
+            .build()
+    }
 
-        let (entry, ash) = Self::create_instance(
-            &actual_required_extensions,
-            required_layers,
-        )?;
+    /// Create a new Vulkan instance using the loader library at an explicit
+    /// path.
+    ///
+    /// # Params
+    ///
+    /// * `path` - The path to the Vulkan loader library.
+    /// * `required_extensions` - All of the extension names required by this
+    ///   application.
+    /// * `required_layers` - All of the layers required by this application.
+    ///
+    /// # Returns
+    ///
+    /// The Vulkan Instance, or
+    /// [crate::InstanceError::CannotLoadVulkanEntry] when the library can't
+    /// be loaded from `path`.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe for the same reasons as [VulkanInstance::new]. Additionally,
+    /// the library at `path` must be a Vulkan loader because its
+    /// initialization code is run when it's loaded.
+    pub unsafe fn new_with_loader_path(
+        path: impl AsRef<Path>,
+        required_extensions: &[String],
+        required_layers: &[String],
+    ) -> InstanceResult<Self> {
+        Self::builder()
+            .loader_path(path.as_ref())
+            .required_extensions(required_extensions)
+            .required_layers(required_layers)
+            .build()
+    }
 
-        let mut vulkan_instance = Self {
-            layers: required_layers.to_vec(),
-            extensions: actual_required_extensions.to_vec(),
+    /// Create a new Vulkan instance with a caller-provided application info
+    /// struct.
+    ///
+    /// This is the maximal-control counterpart to the builder's
+    /// [VulkanInstanceBuilder::engine] option.
+    ///
+    /// # Params
+    ///
+    /// * `application_info` - Used as-is when creating the instance, except
+    ///   that an `api_version` of zero is replaced with the version this
+    ///   library would otherwise request. A non-zero `api_version` always
+    ///   takes precedence.
+    /// * `required_extensions` - All of the extension names required by this
+    ///   application.
+    /// * `required_layers` - All of the layers required by this application.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe for the same reasons as [VulkanInstance::new]. Additionally,
+    /// the strings and any p_next chain referenced by `application_info`
+    /// must stay alive until this call returns.
+    pub unsafe fn new_with_application_info(
+        application_info: vk::ApplicationInfo,
+        required_extensions: &[String],
+        required_layers: &[String],
+    ) -> InstanceResult<Self> {
+        Self::builder()
+            .application_info(application_info)
+            .required_extensions(required_extensions)
+            .required_layers(required_layers)
+            .build()
+    }
+
+    /// Create a builder for configuring the instance before it's created.
+    pub fn builder() -> VulkanInstanceBuilder {
+        VulkanInstanceBuilder::new()
+    }
+
+    /// Create a new Vulkan instance using C string names.
+    ///
+    /// This is the same as [VulkanInstance::new], but accepts names like the
+    /// ones provided by Ash's extension loaders, e.g. `Surface::name()`.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe for the same reasons as [VulkanInstance::new].
+    pub unsafe fn new_with_cstr_names(
+        required_extensions: &[&CStr],
+        required_layers: &[&CStr],
+    ) -> InstanceResult<Self> {
+        Self::new(
+            &ffi::cstrs_to_strings(required_extensions),
+            &ffi::cstrs_to_strings(required_layers),
+        )
+    }
+
+    /// Install a sink which receives all future validation messages.
+    ///
+    /// By default messages are written to the log facade by
+    /// [LogValidationSink]. Installing a sink replaces that behavior
+    /// entirely: the debug callback never writes to the log facade itself, so
+    /// messages are not logged twice. Use [DiscardValidationSink] to silence
+    /// validation output completely. Messages are only reported when debug
+    /// assertions or the `debug_utils_always` feature are enabled.
+    pub fn set_validation_sink(&self, sink: impl ValidationSink + 'static) {
+        self.debug_callback_state.set_sink(Box::new(sink));
+    }
+
+    /// Choose how validation messages are formatted.
+    ///
+    /// Defaults to [DebugMessageFormat::Pretty].
+    pub fn set_debug_message_format(&self, format: DebugMessageFormat) {
+        self.debug_callback_state.set_format(format);
+    }
+
+    /// The format used for validation messages.
+    pub fn debug_message_format(&self) -> DebugMessageFormat {
+        self.debug_callback_state.format()
+    }
+
+    /// The number of ERROR-severity validation messages reported since the
+    /// instance was created.
+    ///
+    /// This is always zero when neither debug assertions nor the
+    /// `debug_utils_always` feature are enabled.
+    pub fn validation_error_count(&self) -> usize {
+        self.debug_callback_state
+            .error_count()
+            .load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Create a guard which panics when dropped if any ERROR-severity
+    /// validation messages are reported while it's alive. See
+    /// [ValidationErrorGuard] for when messages are counted.
+    pub fn validation_error_guard(&self) -> ValidationErrorGuard {
+        ValidationErrorGuard::new(
+            self.debug_callback_state.error_count().clone(),
+        )
+    }
+
+    /// Take ownership of the raw Vulkan objects without destroying them.
+    ///
+    /// The callback state used by the debug messenger is leaked so the
+    /// messenger remains valid until the caller destroys it.
+    ///
+    /// # Returns
+    ///
+    /// The Ash entry, the Ash instance, and the DebugUtils loader and
+    /// messenger if debug logging was enabled.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because:
+    ///   - The caller is responsible for destroying the debug messenger and
+    ///     the instance, in that order.
+    ///   - The entry must outlive the instance.
+    pub unsafe fn into_raw(
+        self,
+    ) -> (
+        ash::Entry,
+        ash::Instance,
+        Option<DebugUtils>,
+        Option<vk::DebugUtilsMessengerEXT>,
+    ) {
+        let this = std::mem::ManuallyDrop::new(self);
+
+        // SAFE because each field is read exactly once and the wrapper is
+        // never dropped.
+        let entry = std::ptr::read(&this.entry);
+        let ash = std::ptr::read(&this.ash);
+        let debug_utils = std::ptr::read(&this.debug_utils);
+        let debug_messenger = this.debug_messenger;
+        drop(std::ptr::read(&this.layers));
+        drop(std::ptr::read(&this.extensions));
+        Box::leak(std::ptr::read(&this.debug_callback_state));
+
+        (entry, ash, debug_utils, debug_messenger)
+    }
+
+    /// Wrap an instance which was created and is owned by someone else, e.g.
+    /// a host application which loads this code as a plugin.
+    ///
+    /// No debug messenger is created and dropping the returned value does not
+    /// destroy the instance. This is the counterpart to
+    /// [VulkanInstance::into_raw]. A messenger created later, e.g. with
+    /// [VulkanInstance::recreate_debug_messenger], belongs to the wrapper and
+    /// is destroyed when it's dropped.
+    ///
+    /// # Params
+    ///
+    /// * `entry` - the Ash entry used to create the instance.
+    /// * `ash` - the externally owned Ash instance.
+    /// * `layers` - the layers which were enabled when creating the instance.
+    /// * `extensions` - the extensions which were enabled when creating the
+    ///   instance. Debug object names are only available when this includes
+    ///   the DebugUtils extension.
+    /// * `api_version` - the `api_version` from the application info used to
+    ///   create the instance, e.g. `vk::API_VERSION_1_2`. Queries which need
+    ///   a newer Vulkan version are skipped when this is too old. This is
+    ///   required, rather than guessed from the loader, because the loader's
+    ///   version can be newer than the version the owner asked for.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because:
+    ///   - The owner must not destroy the instance until this struct, and
+    ///     every device created with it, has been dropped.
+    ///   - The layers, extensions, and API version must match the ones
+    ///     actually used to create the instance.
+    pub unsafe fn from_existing(
+        entry: ash::Entry,
+        ash: ash::Instance,
+        layers: &[String],
+        extensions: &[String],
+        api_version: u32,
+    ) -> Self {
+        let debug_utils_name = DebugUtils::name().to_string_lossy();
+        let debug_utils = if extensions
+            .iter()
+            .any(|extension| *extension == debug_utils_name)
+        {
+            Some(DebugUtils::new(&entry, &ash))
+        } else {
+            None
+        };
+        Self {
+            layers: layers.to_vec(),
+            extensions: extensions.to_vec(),
             debug_messenger: None,
-            debug_utils: None,
+            debug_utils,
+            debug_callback_state: Box::new(DebugCallbackState::new()),
+            debug_message_severity:
+                vk::DebugUtilsMessageSeverityFlagsEXT::empty(),
+            debug_message_types: vk::DebugUtilsMessageTypeFlagsEXT::empty(),
+            owns_instance: false,
+            api_version,
             entry,
             ash,
-        };
+        }
+    }
 
-        vulkan_instance.setup_debug_logger()?;
+    /// True when dropping this struct destroys the Vulkan instance. This is
+    /// false for instances wrapped with [VulkanInstance::from_existing].
+    pub fn owns_instance(&self) -> bool {
+        self.owns_instance
+    }
 
-        Ok(vulkan_instance)
+    /// Returns true when any physical device supports all of the features.
+    ///
+    /// This is a cheap capability probe, e.g. for hiding UI options at
+    /// startup. It stops at the first supported device and only queries
+    /// features, rather than building a full
+    /// [PhysicalDevice](crate::PhysicalDevice) for every device.
+    ///
+    /// # Params
+    ///
+    /// * `features` - the features to look for.
+    pub fn any_device_supports(
+        &self,
+        features: &PhysicalDeviceFeatures,
+    ) -> InstanceResult<bool> {
+        let physical_devices =
+            unsafe { self.ash.enumerate_physical_devices()? };
+        Ok(physical_devices.iter().any(|physical_device| {
+            features.is_supported_by(
+                &PhysicalDeviceFeatures::from_physical_device(
+                    self,
+                    physical_device,
+                ),
+            )
+        }))
+    }
+
+    /// Log a report about every physical device at the info level.
+    ///
+    /// Every device is included, regardless of which features it supports,
+    /// so this is a good first step when debugging "no suitable GPU" issues.
+    /// See [PhysicalDevice::summary](crate::PhysicalDevice::summary) for the
+    /// contents of each report. Enumeration failures are logged as errors.
+    pub fn log_device_report(&self) {
+        let devices = match crate::PhysicalDevice::enumerate_all(self) {
+            Ok(devices) => devices,
+            Err(error) => {
+                log_error!("Unable to enumerate physical devices! {}", error);
+                return;
+            }
+        };
+        log_info!("Found {} physical device(s)", devices.len());
+        for device in devices {
+            log_info!("{}", device.summary());
+        }
     }
 
     /// The raw Ash Entry.
@@ -76,6 +372,17 @@ impl VulkanInstance {
         &self.ash
     }
 
+    /// The Vulkan API version used to create this Vulkan Instance.
+    ///
+    /// This is Vulkan 1.3 unless the loader only supports an older version,
+    /// or a different version was provided with
+    /// [VulkanInstance::new_with_application_info]. Instances wrapped with
+    /// [VulkanInstance::from_existing] report the version provided by the
+    /// caller.
+    pub fn api_version(&self) -> u32 {
+        self.api_version
+    }
+
     /// The layers used to create this Vulkan Instance.
     pub fn layers(&self) -> &[String] {
         &self.layers
@@ -88,7 +395,8 @@ impl VulkanInstance {
 
     /// Set the debug name for an object owned by the provided logical device.
     ///
-    /// This is a no-op for release builds.
+    /// This is a no-op for release builds without the `debug_utils_always`
+    /// feature.
     ///
     /// # Params
     ///
@@ -96,20 +404,24 @@ impl VulkanInstance {
     ///   referenced by the name info struct.
     /// * `name_info` - the name info struct containing the targeted object and
     ///   its new name.
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "debug_utils_always"))]
     pub fn debug_utils_set_object_name(
         &self,
         logical_device: &ash::Device,
         name_info: &vk::DebugUtilsObjectNameInfoEXT,
     ) {
+        let debug_utils = match self.debug_utils.as_ref() {
+            Some(debug_utils) => debug_utils,
+
+            // e.g. a wrapped instance which didn't enable DebugUtils
+            None => return,
+        };
         let result = unsafe {
-            self.debug_utils
-                .as_ref()
-                .unwrap()
+            debug_utils
                 .debug_utils_set_object_name(logical_device.handle(), name_info)
         };
         if result.is_err() {
-            log::warn!(
+            log_warn!(
                 "Unable to set debug name for device! {:#?} {:#?}",
                 name_info,
                 result.err().unwrap()
@@ -119,7 +431,8 @@ impl VulkanInstance {
 
     /// Set the debug name for an object owned by the provided logical device.
     ///
-    /// This is a no-op for release builds.
+    /// This is a no-op for release builds without the `debug_utils_always`
+    /// feature.
     ///
     /// # Params
     ///
@@ -127,7 +440,7 @@ impl VulkanInstance {
     ///   referenced by the name info struct.
     /// * `name_info` - the name info struct containing the targeted object and
     ///   its new name.
-    #[cfg(not(debug_assertions))]
+    #[cfg(not(any(debug_assertions, feature = "debug_utils_always")))]
     pub fn debug_utils_set_object_name(
         &self,
         _logical_device: &ash::Device,
@@ -135,6 +448,72 @@ impl VulkanInstance {
     ) {
         // no-op
     }
+
+    /// Give a debug name to any object which implements [VulkanHandle].
+    ///
+    /// The object type is derived from the handle type, so there is no need
+    /// to build the name info struct by hand.
+    ///
+    /// This is a no-op for release builds without the `debug_utils_always`
+    /// feature.
+    ///
+    /// # Params
+    ///
+    /// * `logical_device` - the logical Vulkan device used to create the
+    ///   object.
+    /// * `handle` - the object to name.
+    /// * `name` - the object's new debug name.
+    #[cfg(any(debug_assertions, feature = "debug_utils_always"))]
+    pub fn name_object<T: VulkanHandle>(
+        &self,
+        logical_device: &ash::Device,
+        handle: &T,
+        name: &str,
+    ) {
+        let object_name = match std::ffi::CString::new(name) {
+            Ok(object_name) => object_name,
+            Err(error) => {
+                log_warn!(
+                    "Unable to use {:?} as a debug name! {:?}",
+                    name,
+                    error
+                );
+                return;
+            }
+        };
+        let name_info = vk::DebugUtilsObjectNameInfoEXT {
+            object_type: T::OBJECT_TYPE,
+            object_handle: unsafe {
+                // SAFE because the raw handle is only used for the duration
+                // of this call.
+                handle.raw_object_handle()
+            },
+            p_object_name: object_name.as_ptr(),
+            ..Default::default()
+        };
+        self.debug_utils_set_object_name(logical_device, &name_info);
+    }
+
+    /// Give a debug name to any object which implements [VulkanHandle].
+    ///
+    /// This is a no-op for release builds without the `debug_utils_always`
+    /// feature.
+    ///
+    /// # Params
+    ///
+    /// * `logical_device` - the logical Vulkan device used to create the
+    ///   object.
+    /// * `handle` - the object to name.
+    /// * `name` - the object's new debug name.
+    #[cfg(not(any(debug_assertions, feature = "debug_utils_always")))]
+    pub fn name_object<T: VulkanHandle>(
+        &self,
+        _logical_device: &ash::Device,
+        _handle: &T,
+        _name: &str,
+    ) {
+        // no-op
+    }
 }
 
 impl Drop for VulkanInstance {
@@ -148,16 +527,18 @@ impl Drop for VulkanInstance {
     ///   - use Vulkan validation layers to verify correct resource management.
     fn drop(&mut self) {
         unsafe {
-            if self.debug_utils.is_some() {
-                self.debug_utils
-                    .as_ref()
-                    .unwrap()
-                    .destroy_debug_utils_messenger(
-                        self.debug_messenger.unwrap(),
-                        None,
-                    );
+            // The messenger is always destroyed, even for borrowed instances,
+            // because its user data points at the callback state which is
+            // freed along with this struct.
+            if let (Some(debug_utils), Some(debug_messenger)) =
+                (&self.debug_utils, self.debug_messenger)
+            {
+                debug_utils
+                    .destroy_debug_utils_messenger(debug_messenger, None);
+            }
+            if self.owns_instance {
+                self.ash.destroy_instance(None);
             }
-            self.ash.destroy_instance(None);
         }
     }
 }
@@ -168,7 +549,11 @@ impl Debug for VulkanInstance {
             .debug_struct("VulkanInstance")
             .field("layers", &self.layers)
             .field("extensions", &self.extensions)
-            .field("is_debug_enabled", &cfg!(debug_assertions))
+            .field(
+                "is_debug_enabled",
+                &cfg!(any(debug_assertions, feature = "debug_utils_always")),
+            )
+            .field("owns_instance", &self.owns_instance)
             .finish()
     }
 }
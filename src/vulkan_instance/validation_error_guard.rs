@@ -0,0 +1,58 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Panics when dropped if the instance reported any ERROR-severity
+/// validation messages while the guard was alive.
+///
+/// This is intended for tests, where it turns a smoke test into a check
+/// that the code under test is valid Vulkan usage. The guard does not
+/// borrow the instance, so it can be held across any use of the instance.
+///
+/// The panic is unconditional, so a guard held in application code panics
+/// in every build profile. However, messages are only counted when debug
+/// assertions or the `debug_utils_always` feature are enabled, so without
+/// either the guard never panics.
+///
+/// # Examples
+///
+/// ```
+/// use ccthw_ash_instance::VulkanInstance;
+///
+/// let instance = unsafe { VulkanInstance::new(&[], &[]).unwrap() };
+/// let _guard = instance.validation_error_guard();
+///
+/// // ... exercise the instance ...
+/// ```
+#[derive(Debug)]
+pub struct ValidationErrorGuard {
+    error_count: Arc<AtomicUsize>,
+    initial_count: usize,
+}
+
+impl ValidationErrorGuard {
+    pub(super) fn new(error_count: Arc<AtomicUsize>) -> Self {
+        let initial_count = error_count.load(Ordering::SeqCst);
+        Self {
+            error_count,
+            initial_count,
+        }
+    }
+
+    /// The number of ERROR-severity messages reported since the guard was
+    /// created.
+    pub fn errors_since_created(&self) -> usize {
+        self.error_count.load(Ordering::SeqCst) - self.initial_count
+    }
+}
+
+impl Drop for ValidationErrorGuard {
+    fn drop(&mut self) {
+        let errors = self.errors_since_created();
+        // Avoid a double-panic which would abort the test runner.
+        if errors > 0 && !std::thread::panicking() {
+            panic!("{} Vulkan validation error(s) were reported", errors);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use ash::vk::{self, DebugUtilsMessageSeverityFlagsEXT};
+
+/// Controls how validation messages are formatted as text.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum DebugMessageFormat {
+    /// Multi-line output with the message, followed by one line for each
+    /// object the message refers to. The message text itself is left
+    /// untouched. This is easy to read interactively.
+    #[default]
+    Pretty,
+
+    /// Single-line output. This is better suited to log aggregation where
+    /// each line is treated as a separate event.
+    Raw,
+}
+
+/// A Vulkan object referenced by a validation message.
+#[derive(Debug, Copy, Clone)]
+pub struct ValidationObject<'a> {
+    pub object_type: vk::ObjectType,
+    pub object_handle: u64,
+
+    /// The debug name given to the object, if any.
+    pub object_name: Option<&'a str>,
+}
+
+/// The parsed fields of a single message from the DebugUtils messenger.
+#[derive(Debug, Copy, Clone)]
+pub struct ValidationMessage<'a> {
+    pub severity: DebugUtilsMessageSeverityFlagsEXT,
+    pub message_type: vk::DebugUtilsMessageTypeFlagsEXT,
+    pub message_id_name: &'a str,
+    pub message_id_number: i32,
+    pub message: &'a str,
+
+    /// The objects which the message refers to, in the order reported by
+    /// the messenger.
+    pub objects: &'a [ValidationObject<'a>],
+
+    /// The format configured on the VulkanInstance.
+    pub format: DebugMessageFormat,
+}
+
+impl<'a> ValidationMessage<'a> {
+    /// Format the full message as text according to the configured format.
+    pub fn formatted(&self) -> String {
+        match self.format {
+            DebugMessageFormat::Pretty => {
+                let mut pretty_message = std::format!(
+                    "VULKAN DEBUG CALLBACK - {:?}::{:?} - [{} ({})]\n\n{}",
+                    self.severity,
+                    self.message_type,
+                    self.message_id_name,
+                    self.message_id_number,
+                    self.message
+                );
+                if !self.objects.is_empty() {
+                    pretty_message.push_str("\n\nObjects:");
+                }
+                for (index, object) in self.objects.iter().enumerate() {
+                    pretty_message.push_str(&std::format!(
+                        "\n  {}: {:?} {:#x}",
+                        index,
+                        object.object_type,
+                        object.object_handle
+                    ));
+                    if let Some(name) = object.object_name {
+                        pretty_message.push_str(&std::format!(" {:?}", name));
+                    }
+                }
+                pretty_message
+            }
+            DebugMessageFormat::Raw => std::format!(
+                "VULKAN DEBUG CALLBACK - {:?}::{:?} - [{} ({})] {}",
+                self.severity,
+                self.message_type,
+                self.message_id_name,
+                self.message_id_number,
+                self.message
+            ),
+        }
+    }
+}
+
+/// Types which implement this trait can receive validation messages from the
+/// DebugUtils messenger.
+///
+/// The sink can be invoked from any thread which calls into Vulkan, so
+/// implementations must be both Send and Sync.
+///
+/// # Examples
+///
+/// ```
+/// use ccthw_ash_instance::{ValidationMessage, ValidationSink};
+///
+/// struct PrintSink;
+///
+/// impl ValidationSink for PrintSink {
+///     fn on_message(&self, message: &ValidationMessage) {
+///         println!(
+///             "{:?} [{}] {}",
+///             message.severity, message.message_id_name, message.message
+///         );
+///     }
+/// }
+/// ```
+pub trait ValidationSink: Send + Sync {
+    /// Called once for every message reported by the DebugUtils messenger.
+    fn on_message(&self, message: &ValidationMessage);
+}
+
+/// The default validation sink. Messages are formatted according to their
+/// [DebugMessageFormat] and written to the log facade at a level matching
+/// their severity.
+///
+/// Messages are discarded when the `logging` feature is disabled.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct LogValidationSink;
+
+impl ValidationSink for LogValidationSink {
+    fn on_message(&self, message: &ValidationMessage) {
+        let full_message = message.formatted();
+
+        match message.severity {
+            DebugUtilsMessageSeverityFlagsEXT::VERBOSE => {
+                log_trace!("{}", full_message);
+            }
+
+            DebugUtilsMessageSeverityFlagsEXT::INFO => {
+                log_trace!("{}", full_message);
+            }
+
+            DebugUtilsMessageSeverityFlagsEXT::WARNING => {
+                log_warn!("{}", full_message);
+            }
+
+            DebugUtilsMessageSeverityFlagsEXT::ERROR => {
+                log_error!("{}", full_message);
+            }
+
+            _ => {
+                log_warn!("?? {}", full_message);
+            }
+        }
+    }
+}
+
+/// A validation sink which discards every message.
+///
+/// Install this sink to keep validation enabled, e.g. for
+/// [VulkanInstance::validation_error_count], without writing anything to the
+/// log facade.
+///
+/// [VulkanInstance::validation_error_count]:
+///     crate::VulkanInstance::validation_error_count
+#[derive(Debug, Copy, Clone, Default)]
+pub struct DiscardValidationSink;
+
+impl ValidationSink for DiscardValidationSink {
+    fn on_message(&self, _message: &ValidationMessage) {
+        // no-op
+    }
+}
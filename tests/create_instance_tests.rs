@@ -2,8 +2,19 @@ mod common;
 
 use {
     anyhow::Result,
+    ash::vk,
     assert2::{check, let_assert},
-    ccthw_ash_instance::{InstanceError, VulkanInstance},
+    ccthw_ash_instance::{
+        DebugMessageFormat, DiscardValidationSink, InstanceError,
+        ValidationMessage, ValidationObject, ValidationSink, VulkanInstance,
+    },
+    std::{
+        ffi::CStr,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    },
 };
 
 #[test]
@@ -17,6 +28,225 @@ pub fn create_instance() -> Result<()> {
     Ok(())
 }
 
+#[test]
+pub fn create_instance_with_builder() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::builder().build()? };
+
+    log::info!("Successfully Created Instance - {}", instance);
+
+    Ok(())
+}
+
+#[test]
+pub fn create_instance_with_engine_name() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe {
+        VulkanInstance::builder()
+            .engine("test engine", vk::make_api_version(0, 2, 1, 0))
+            .build()?
+    };
+
+    log::info!("Successfully Created Instance - {}", instance);
+
+    Ok(())
+}
+
+#[test]
+pub fn create_instance_with_empty_flags() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe {
+        VulkanInstance::builder()
+            .instance_flags(vk::InstanceCreateFlags::empty())
+            .build()?
+    };
+
+    log::info!("Successfully Created Instance - {}", instance);
+
+    Ok(())
+}
+
+#[test]
+pub fn create_instance_with_best_practices() -> Result<()> {
+    common::setup_logger();
+
+    let validation_layer = "VK_LAYER_KHRONOS_validation".to_owned();
+    let entry = unsafe { ash::Entry::load()? };
+    let layer_is_available = entry
+        .enumerate_instance_layer_properties()?
+        .iter()
+        .any(|layer| unsafe {
+            CStr::from_ptr(layer.layer_name.as_ptr()).to_string_lossy()
+                == validation_layer
+        });
+    if !layer_is_available {
+        return Ok(());
+    }
+
+    // Listing the validation features extension explicitly should not add it
+    // a second time.
+    let validation_features = vk::ExtValidationFeaturesFn::name()
+        .to_string_lossy()
+        .to_string();
+    let instance = unsafe {
+        VulkanInstance::builder()
+            .required_layers(&[validation_layer])
+            .required_extensions(&[validation_features.clone()])
+            .enable_best_practices()
+            .build()?
+    };
+    check!(
+        instance
+            .extensions()
+            .iter()
+            .filter(|name| **name == validation_features)
+            .count()
+            == 1
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn engine_name_with_nul_should_fail() {
+    common::setup_logger();
+
+    let_assert!(
+        Err(InstanceError::InvalidName(name)) =
+            unsafe { VulkanInstance::builder().engine("bad\0name", 0).build() }
+    );
+    check!(name == "bad\0name");
+}
+
+#[test]
+pub fn take_ownership_of_raw_instance() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let (_entry, ash, debug_utils, debug_messenger) =
+        unsafe { instance.into_raw() };
+
+    unsafe {
+        if let (Some(debug_utils), Some(debug_messenger)) =
+            (debug_utils, debug_messenger)
+        {
+            debug_utils.destroy_debug_utils_messenger(debug_messenger, None);
+        }
+        ash.destroy_instance(None);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn wrap_externally_owned_instance() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let layers = instance.layers().to_vec();
+    let extensions = instance.extensions().to_vec();
+    let api_version = instance.api_version();
+    let (entry, ash, debug_utils, debug_messenger) =
+        unsafe { instance.into_raw() };
+
+    let wrapped = unsafe {
+        VulkanInstance::from_existing(
+            entry,
+            ash.clone(),
+            &layers,
+            &extensions,
+            api_version,
+        )
+    };
+    check!(!wrapped.owns_instance());
+    check!(wrapped.extensions() == extensions);
+    check!(wrapped.api_version() == api_version);
+    drop(wrapped);
+
+    // the instance is still valid because the wrapper didn't destroy it
+    unsafe {
+        if let (Some(debug_utils), Some(debug_messenger)) =
+            (debug_utils, debug_messenger)
+        {
+            debug_utils.destroy_debug_utils_messenger(debug_messenger, None);
+        }
+        ash.destroy_instance(None);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn wrapped_instances_destroy_their_own_debug_messenger() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let layers = instance.layers().to_vec();
+    let extensions = instance.extensions().to_vec();
+    let api_version = instance.api_version();
+    let (entry, ash, debug_utils, debug_messenger) =
+        unsafe { instance.into_raw() };
+
+    let mut wrapped = unsafe {
+        VulkanInstance::from_existing(
+            entry,
+            ash.clone(),
+            &layers,
+            &extensions,
+            api_version,
+        )
+    };
+    let result = wrapped.recreate_debug_messenger(
+        vk::DebugUtilsMessageSeverityFlagsEXT::WARNING
+            | vk::DebugUtilsMessageSeverityFlagsEXT::ERROR,
+        vk::DebugUtilsMessageTypeFlagsEXT::GENERAL
+            | vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION,
+    );
+    if debug_utils.is_some() {
+        result?;
+    }
+    drop(wrapped);
+
+    unsafe {
+        if let (Some(debug_utils), Some(debug_messenger)) =
+            (debug_utils, debug_messenger)
+        {
+            // A messenger left behind by the wrapper would receive this
+            // message and read its freed callback state.
+            let message = std::ffi::CStr::from_bytes_with_nul(
+                b"after the wrapper was dropped\0",
+            )?;
+            debug_utils.submit_debug_utils_message(
+                vk::DebugUtilsMessageSeverityFlagsEXT::WARNING,
+                vk::DebugUtilsMessageTypeFlagsEXT::GENERAL,
+                &vk::DebugUtilsMessengerCallbackDataEXT {
+                    p_message: message.as_ptr(),
+                    ..Default::default()
+                },
+            );
+            debug_utils.destroy_debug_utils_messenger(debug_messenger, None);
+        }
+        ash.destroy_instance(None);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn instance_api_version_is_at_most_vulkan_13() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    check!(instance.api_version() >= vk::API_VERSION_1_0);
+    check!(instance.api_version() <= vk::API_VERSION_1_3);
+
+    Ok(())
+}
+
 #[test]
 pub fn send_between_threads() -> Result<()> {
     common::setup_logger();
@@ -44,6 +274,63 @@ pub fn missing_extensions_should_fail() {
     check!(extensions.contains(&"bogus_extension_name".to_owned()));
 }
 
+#[test]
+pub fn missing_cstr_extensions_should_fail() {
+    common::setup_logger();
+
+    let bogus_name =
+        std::ffi::CStr::from_bytes_with_nul(b"bogus_extension_name\0").unwrap();
+    let_assert!(
+        Err(InstanceError::MissingExtensions(extensions)) =
+            unsafe { VulkanInstance::new_with_cstr_names(&[bogus_name], &[]) }
+    );
+    check!(extensions.contains(&"bogus_extension_name".to_owned()));
+}
+
+#[test]
+pub fn device_lost_should_be_recoverable() {
+    check!(InstanceError::from(vk::Result::ERROR_DEVICE_LOST).is_recoverable());
+    check!(!InstanceError::MissingLayers(vec![]).is_recoverable());
+}
+
+#[test]
+pub fn no_physical_devices_should_not_be_recoverable() {
+    let error = InstanceError::NoPhysicalDevices;
+    check!(!error.is_recoverable());
+    check!(error.to_string() == "No Vulkan physical devices are available");
+}
+
+#[test]
+pub fn device_lost_results_have_a_dedicated_variant() {
+    let_assert!(
+        InstanceError::DeviceLost =
+            InstanceError::from(vk::Result::ERROR_DEVICE_LOST)
+    );
+    check!(InstanceError::DeviceLost.is_recoverable());
+    let_assert!(
+        InstanceError::UnexpectedVulkanError(vk::Result::TIMEOUT) =
+            InstanceError::from(vk::Result::TIMEOUT)
+    );
+}
+
+#[test]
+pub fn probe_instance_extension_support() -> Result<()> {
+    common::setup_logger();
+
+    let entry = unsafe { ash::Entry::load()? };
+    check!(!VulkanInstance::is_extension_available(
+        &entry,
+        "bogus_extension_name"
+    )?);
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    for extension in instance.extensions() {
+        check!(VulkanInstance::is_extension_available(&entry, extension)?);
+    }
+
+    Ok(())
+}
+
 #[test]
 pub fn missing_layers_should_fail() {
     common::setup_logger();
@@ -56,3 +343,243 @@ pub fn missing_layers_should_fail() {
     let_assert!(InstanceError::MissingLayers(missing_layers) = e);
     check!(missing_layers.contains(&"bogus_layer_name".to_owned()));
 }
+
+struct TestSink;
+
+impl ValidationSink for TestSink {
+    fn on_message(&self, message: &ValidationMessage) {
+        log::info!("Test sink received {}", message.message_id_name);
+    }
+}
+
+#[test]
+pub fn install_validation_sink() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    instance.set_validation_sink(TestSink);
+
+    log::info!("Installed a validation sink - {}", instance);
+
+    Ok(())
+}
+
+struct CountingSink(Arc<AtomicUsize>);
+
+impl ValidationSink for CountingSink {
+    fn on_message(&self, _message: &ValidationMessage) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Send a message through the instance's debug messenger.
+fn submit_error_message(instance: &VulkanInstance) -> Result<()> {
+    let message =
+        std::ffi::CStr::from_bytes_with_nul(b"submitted by the test\0")?;
+    unsafe {
+        instance.debug_utils().unwrap().submit_debug_utils_message(
+            vk::DebugUtilsMessageSeverityFlagsEXT::ERROR,
+            vk::DebugUtilsMessageTypeFlagsEXT::GENERAL,
+            &vk::DebugUtilsMessengerCallbackDataEXT {
+                p_message: message.as_ptr(),
+                ..Default::default()
+            },
+        );
+    }
+    Ok(())
+}
+
+#[test]
+pub fn discard_validation_messages() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    if instance.debug_utils().is_none() {
+        // messages are only reported when DebugUtils is loaded
+        return Ok(());
+    }
+
+    let received = Arc::new(AtomicUsize::new(0));
+    instance.set_validation_sink(CountingSink(received.clone()));
+    submit_error_message(&instance)?;
+    check!(received.load(Ordering::SeqCst) == 1);
+    check!(instance.validation_error_count() == 1);
+
+    instance.set_validation_sink(DiscardValidationSink);
+    submit_error_message(&instance)?;
+
+    // errors are still counted even though the message went nowhere
+    check!(received.load(Ordering::SeqCst) == 1);
+    check!(instance.validation_error_count() == 2);
+
+    Ok(())
+}
+
+#[test]
+pub fn set_debug_message_format() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    check!(instance.debug_message_format() == DebugMessageFormat::Pretty);
+
+    instance.set_debug_message_format(DebugMessageFormat::Raw);
+    check!(instance.debug_message_format() == DebugMessageFormat::Raw);
+
+    Ok(())
+}
+
+#[test]
+pub fn recreate_debug_messenger() -> Result<()> {
+    common::setup_logger();
+
+    let mut instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let result = instance.recreate_debug_messenger(
+        vk::DebugUtilsMessageSeverityFlagsEXT::WARNING
+            | vk::DebugUtilsMessageSeverityFlagsEXT::ERROR,
+        vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION,
+    );
+
+    if cfg!(any(debug_assertions, feature = "debug_utils_always")) {
+        check!(result.is_ok());
+    } else {
+        let_assert!(Err(InstanceError::DebugUtilsNotLoaded) = result);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn mute_performance_messages() -> Result<()> {
+    common::setup_logger();
+
+    let mut instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let types = vk::DebugUtilsMessageTypeFlagsEXT::GENERAL
+        | vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION;
+    let result = instance.set_debug_message_types(types);
+
+    if cfg!(any(debug_assertions, feature = "debug_utils_always")) {
+        check!(result.is_ok());
+        check!(instance.debug_message_types() == types);
+    } else {
+        let_assert!(Err(InstanceError::DebugUtilsNotLoaded) = result);
+        check!(instance.debug_message_types().is_empty());
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn new_instance_has_no_validation_errors() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let guard = instance.validation_error_guard();
+
+    check!(instance.validation_error_count() == 0);
+    check!(guard.errors_since_created() == 0);
+
+    Ok(())
+}
+
+#[test]
+pub fn missing_loader_library_should_fail() {
+    common::setup_logger();
+
+    let_assert!(
+        Err(InstanceError::CannotLoadVulkanEntry(_)) = unsafe {
+            VulkanInstance::new_with_loader_path(
+                "/definitely/not/a/vulkan/loader.so",
+                &[],
+                &[],
+            )
+        }
+    );
+}
+
+#[test]
+pub fn log_device_report() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    instance.log_device_report();
+
+    Ok(())
+}
+
+#[test]
+pub fn create_instance_with_application_info() -> Result<()> {
+    common::setup_logger();
+
+    let app_name = std::ffi::CString::new("application info test")?;
+    let app_info = vk::ApplicationInfo {
+        p_application_name: app_name.as_ptr(),
+        application_version: vk::make_api_version(0, 2, 3, 4),
+        api_version: vk::API_VERSION_1_0,
+        ..Default::default()
+    };
+    let instance = unsafe {
+        VulkanInstance::new_with_application_info(app_info, &[], &[])?
+    };
+    check!(instance.api_version() == vk::API_VERSION_1_0);
+
+    // A zero api_version is replaced with the default version.
+    let default_instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let instance = unsafe {
+        VulkanInstance::new_with_application_info(
+            vk::ApplicationInfo {
+                api_version: 0,
+                ..app_info
+            },
+            &[],
+            &[],
+        )?
+    };
+    check!(instance.api_version() == default_instance.api_version());
+
+    Ok(())
+}
+
+#[test]
+pub fn instance_extension_loaders() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    // The surface extension is never added automatically.
+    let_assert!(
+        Err(InstanceError::MissingExtensions(extensions)) =
+            instance.surface_loader().map(|_| ())
+    );
+    check!(extensions == vec!["VK_KHR_surface".to_owned()]);
+
+    check!(
+        instance.debug_utils().is_some()
+            == cfg!(any(debug_assertions, feature = "debug_utils_always"))
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn pretty_messages_should_not_split_the_message_text() {
+    common::setup_logger();
+
+    let objects = [ValidationObject {
+        object_type: vk::ObjectType::BUFFER,
+        object_handle: 0x1234,
+        object_name: Some("vertex buffer"),
+    }];
+    let message = ValidationMessage {
+        severity: vk::DebugUtilsMessageSeverityFlagsEXT::ERROR,
+        message_type: vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION,
+        message_id_name: "VUID-test",
+        message_id_number: 1,
+        message: "loading C:/my; shaders/test.spv failed",
+        objects: &objects,
+        format: DebugMessageFormat::Pretty,
+    };
+
+    let formatted = message.formatted();
+    check!(formatted.contains("loading C:/my; shaders/test.spv failed"));
+    check!(formatted.contains("0: BUFFER 0x1234 \"vertex buffer\""));
+}
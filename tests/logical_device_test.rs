@@ -1,9 +1,10 @@
 use {
     anyhow::Result,
     ash::vk,
+    assert2::let_assert,
     ccthw_ash_instance::{
-        LogicalDevice, PhysicalDevice, PhysicalDeviceFeatures, QueueFamilyInfo,
-        VulkanInstance,
+        DeviceBuilder, InstanceError, LogicalDevice, PhysicalDevice,
+        PhysicalDeviceFeatures, QueueFamilyInfo, VulkanHandle, VulkanInstance,
     },
 };
 
@@ -15,6 +16,7 @@ pub fn create_logical_device() -> Result<()> {
 
     // Create a Vulkan instance.
     let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let _validation_guard = instance.validation_error_guard();
 
     // Pick a suitable physical device
     let physical_device = PhysicalDevice::enumerate_supported_devices(
@@ -56,6 +58,12 @@ pub fn create_logical_device() -> Result<()> {
 
     log::info!("Created Logical device! {:#?}", logical_device);
 
+    assert2::check!(logical_device
+        .enabled_features()
+        .is_supported_by(logical_device.physical_device().features()));
+
+    logical_device.wait_idle()?;
+
     Ok(())
 }
 
@@ -118,3 +126,898 @@ pub fn send_between_threads() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+pub fn name_logical_device() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let physical_device = PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?
+    .into_iter()
+    .next()
+    .unwrap();
+
+    let mut family_info = QueueFamilyInfo::new(0);
+    family_info.add_queue_priority(1.0);
+
+    let logical_device = unsafe {
+        LogicalDevice::new(&instance, physical_device, &[], &[family_info])?
+    };
+
+    instance.name_object(
+        unsafe { logical_device.raw() },
+        &logical_device,
+        "test logical device",
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn protected_queues_require_protected_memory() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let physical_device = PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?
+    .into_iter()
+    .next()
+    .unwrap();
+
+    let mut family_info = QueueFamilyInfo::new(0);
+    family_info.add_queue_priority(1.0);
+    family_info.set_protected(true);
+
+    let_assert!(
+        Err(InstanceError::ProtectedMemoryNotEnabled) = unsafe {
+            LogicalDevice::new(&instance, physical_device, &[], &[family_info])
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn protected_queue_flags_require_protected_memory() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let physical_device = PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?
+    .into_iter()
+    .next()
+    .unwrap();
+
+    let mut family_info = QueueFamilyInfo::new(0);
+    family_info.add_queue_priority(1.0);
+    family_info.set_flags(vk::DeviceQueueCreateFlags::PROTECTED);
+    assert2::check!(
+        family_info.flags() == vk::DeviceQueueCreateFlags::PROTECTED
+    );
+
+    let_assert!(
+        Err(InstanceError::ProtectedMemoryNotEnabled) = unsafe {
+            LogicalDevice::new(&instance, physical_device, &[], &[family_info])
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn missing_device_extensions_should_fail() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let physical_device = PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?
+    .into_iter()
+    .next()
+    .unwrap();
+
+    let mut family_info = QueueFamilyInfo::new(0);
+    family_info.add_queue_priority(1.0);
+
+    let_assert!(
+        Err(InstanceError::MissingDeviceExtensions(missing)) = unsafe {
+            LogicalDevice::new(
+                &instance,
+                physical_device,
+                &["bogus_extension_name".to_owned()],
+                &[family_info],
+            )
+        }
+    );
+    assert2::check!(missing == vec!["bogus_extension_name".to_owned()]);
+
+    Ok(())
+}
+
+#[test]
+pub fn create_compute_device() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let _validation_guard = instance.validation_error_guard();
+
+    let (logical_device, compute_queue) = unsafe {
+        LogicalDevice::new_compute(
+            &instance,
+            &PhysicalDeviceFeatures::default(),
+        )?
+    };
+
+    log::info!(
+        "Created compute device {} with queue {:?}",
+        logical_device,
+        compute_queue
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn queue_family_info_should_not_exceed_queue_count() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let physical_device = PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?
+    .into_iter()
+    .next()
+    .unwrap();
+    let queue_count = physical_device.queue_family_properties()[0].queue_count;
+
+    let mut family_info = QueueFamilyInfo::with_max_queues(&physical_device, 0);
+    for _ in 0..queue_count {
+        family_info.try_add_queue_priority(1.0)?;
+    }
+
+    let_assert!(
+        Err(InstanceError::TooManyQueues(0, max_queue_count)) =
+            family_info.try_add_queue_priority(1.0)
+    );
+    assert2::check!(max_queue_count == queue_count);
+
+    Ok(())
+}
+
+#[test]
+pub fn display_queue_family_info() {
+    let mut family_info = QueueFamilyInfo::new(2);
+    family_info.add_queue_priority(1.0);
+    family_info.add_queue_priority(1.0);
+    family_info.add_queue_priority(0.5);
+
+    assert2::check!(
+        family_info.to_string() == "family 2: 3 queues [1.0, 1.0, 0.5]"
+    );
+}
+
+#[test]
+pub fn merge_queue_family_infos_by_index() {
+    let mut graphics = QueueFamilyInfo::new(0);
+    graphics.add_queue_priority(1.0);
+    let mut present = QueueFamilyInfo::new(0);
+    present.add_queue_priority(0.5);
+    let mut compute = QueueFamilyInfo::new(1);
+    compute.add_queue_priority(1.0);
+
+    let merged = QueueFamilyInfo::merge_by_index(&[graphics, compute, present]);
+
+    assert2::check!(merged.len() == 2);
+    assert2::check!(merged[0].to_string() == "family 0: 2 queues [1.0, 0.5]");
+    assert2::check!(merged[1].to_string() == "family 1: 1 queue [1.0]");
+}
+
+#[test]
+pub fn merge_should_keep_infos_with_different_flags_separate() {
+    let mut unprotected = QueueFamilyInfo::new(0);
+    unprotected.add_queue_priority(1.0);
+    let mut protected = QueueFamilyInfo::new(0);
+    protected.add_queue_priority(0.5);
+    protected.set_protected(true);
+
+    let merged = QueueFamilyInfo::merge_by_index(&[unprotected, protected]);
+
+    assert2::check!(merged.len() == 2);
+    assert2::check!(merged[0].flags().is_empty());
+    assert2::check!(merged[0].queue_count() == 1);
+    assert2::check!(merged[1].flags() == vk::DeviceQueueCreateFlags::PROTECTED);
+    assert2::check!(merged[1].queue_count() == 1);
+}
+
+#[test]
+pub fn merged_infos_should_not_exceed_queue_count() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let physical_device = PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?
+    .into_iter()
+    .next()
+    .unwrap();
+    let queue_count = physical_device.queue_family_properties()[0].queue_count;
+
+    // Each info is valid on its own, but not once they're merged.
+    let priorities = vec![1.0; queue_count as usize];
+    let first = QueueFamilyInfo::from_priorities(0, priorities.clone())?;
+    let second = QueueFamilyInfo::from_priorities(0, priorities)?;
+
+    let_assert!(
+        Err(InstanceError::TooManyQueues(0, max_queue_count)) = unsafe {
+            LogicalDevice::new(
+                &instance,
+                physical_device,
+                &[],
+                &[first, second],
+            )
+        }
+    );
+    assert2::check!(max_queue_count == queue_count);
+
+    Ok(())
+}
+
+#[test]
+pub fn swapchain_loader_requires_the_extension() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let physical_device = PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?
+    .into_iter()
+    .next()
+    .unwrap();
+
+    let mut family_info = QueueFamilyInfo::new(0);
+    family_info.add_queue_priority(1.0);
+
+    let logical_device = unsafe {
+        LogicalDevice::new(&instance, physical_device, &[], &[family_info])?
+    };
+
+    // the loader isn't Debug, so only the error is inspected
+    let_assert!(
+        Some(InstanceError::MissingDeviceExtensions(missing)) =
+            logical_device.swapchain_loader(&instance).err()
+    );
+    assert2::check!(missing == vec!["VK_KHR_swapchain".to_owned()]);
+
+    Ok(())
+}
+
+#[test]
+pub fn build_device_with_queues() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let _validation_guard = instance.validation_error_guard();
+
+    let (logical_device, queues) = unsafe {
+        DeviceBuilder::new(&instance)
+            .queue(vk::QueueFlags::COMPUTE)
+            .build()?
+    };
+
+    assert2::check!(queues.len() == 1);
+    assert2::check!(queues[0] != vk::Queue::null());
+    log::info!("Built {}", logical_device);
+
+    Ok(())
+}
+
+#[test]
+pub fn build_device_should_explain_rejections() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let result = unsafe {
+        DeviceBuilder::new(&instance)
+            .extension("bogus_extension_name")
+            .build()
+    };
+
+    let_assert!(Some(InstanceError::NoSuitableDevice(reasons)) = result.err());
+    assert2::check!(!reasons.is_empty());
+    assert2::check!(reasons
+        .iter()
+        .all(|reason| reason.contains("bogus_extension_name")));
+
+    Ok(())
+}
+
+#[test]
+pub fn logical_device_uses_instance_layers() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let (logical_device, _) = unsafe {
+        DeviceBuilder::new(&instance)
+            .queue(vk::QueueFlags::COMPUTE)
+            .build()?
+    };
+
+    assert2::check!(logical_device.active_layers() == instance.layers());
+
+    Ok(())
+}
+
+#[test]
+pub fn queue_family_info_from_priorities() -> Result<()> {
+    let family_info = QueueFamilyInfo::from_priorities(1, [1.0, 0.5])?;
+    assert2::check!(family_info.to_string() == "family 1: 2 queues [1.0, 0.5]");
+
+    let_assert!(
+        Err(InstanceError::InvalidQueuePriority(1, priority)) =
+            QueueFamilyInfo::from_priorities(1, [1.0, 1.5])
+    );
+    assert2::check!(priority == 1.5);
+
+    Ok(())
+}
+
+#[test]
+pub fn insert_queue_debug_label() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let _validation_guard = instance.validation_error_guard();
+
+    let (_logical_device, queues) = unsafe {
+        DeviceBuilder::new(&instance)
+            .queue(vk::QueueFlags::COMPUTE)
+            .build()?
+    };
+    instance.queue_insert_label(queues[0], "frame 0", [1.0, 0.0, 0.0, 1.0]);
+
+    Ok(())
+}
+
+#[test]
+pub fn debug_scope_should_begin_and_end_a_region() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let _validation_guard = instance.validation_error_guard();
+
+    let (logical_device, _queues) = unsafe {
+        DeviceBuilder::new(&instance)
+            .queue(vk::QueueFlags::COMPUTE)
+            .build()?
+    };
+    let (queue_family_index, _) = logical_device
+        .physical_device()
+        .queue_families_with_flags(vk::QueueFlags::COMPUTE)
+        .next()
+        .unwrap();
+
+    unsafe {
+        let device = logical_device.raw();
+        let command_pool = device.create_command_pool(
+            &vk::CommandPoolCreateInfo {
+                queue_family_index,
+                ..Default::default()
+            },
+            None,
+        )?;
+        let command_buffer = device.allocate_command_buffers(
+            &vk::CommandBufferAllocateInfo {
+                command_pool,
+                level: vk::CommandBufferLevel::PRIMARY,
+                command_buffer_count: 1,
+                ..Default::default()
+            },
+        )?[0];
+        device.begin_command_buffer(
+            command_buffer,
+            &vk::CommandBufferBeginInfo::default(),
+        )?;
+        {
+            let _scope = instance.debug_scope(
+                command_buffer,
+                "outer",
+                [0.0, 1.0, 0.0, 1.0],
+            );
+            let _inner_scope =
+                instance.debug_scope(command_buffer, "inner", [0.0; 4]);
+        }
+        device.end_command_buffer(command_buffer)?;
+        device.destroy_command_pool(command_pool, None);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn debug_scope_should_skip_names_with_nul_bytes() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let _validation_guard = instance.validation_error_guard();
+
+    let (logical_device, _queues) = unsafe {
+        DeviceBuilder::new(&instance)
+            .queue(vk::QueueFlags::COMPUTE)
+            .build()?
+    };
+    let (queue_family_index, _) = logical_device
+        .physical_device()
+        .queue_families_with_flags(vk::QueueFlags::COMPUTE)
+        .next()
+        .unwrap();
+
+    unsafe {
+        let device = logical_device.raw();
+        let command_pool = device.create_command_pool(
+            &vk::CommandPoolCreateInfo {
+                queue_family_index,
+                ..Default::default()
+            },
+            None,
+        )?;
+        let command_buffer = device.allocate_command_buffers(
+            &vk::CommandBufferAllocateInfo {
+                command_pool,
+                level: vk::CommandBufferLevel::PRIMARY,
+                command_buffer_count: 1,
+                ..Default::default()
+            },
+        )?[0];
+        device.begin_command_buffer(
+            command_buffer,
+            &vk::CommandBufferBeginInfo::default(),
+        )?;
+        // The label can't be converted to a C string, so no region is
+        // begun and the guard must not end one either.
+        assert2::check!(!instance.cmd_begin_label(
+            command_buffer,
+            "bad\0name",
+            [0.0; 4]
+        ));
+        {
+            let _scope =
+                instance.debug_scope(command_buffer, "bad\0name", [0.0; 4]);
+        }
+        device.end_command_buffer(command_buffer)?;
+        device.destroy_command_pool(command_pool, None);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn retrieve_every_queue_in_a_family() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let _validation_guard = instance.validation_error_guard();
+
+    let physical_device = PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?
+    .into_iter()
+    .next()
+    .unwrap();
+    let queue_count = physical_device.queue_family_properties()[0]
+        .queue_count
+        .min(2);
+
+    let family_info = QueueFamilyInfo::from_priorities(
+        0,
+        [1.0, 0.5].into_iter().take(queue_count as usize),
+    )?;
+    let logical_device = unsafe {
+        LogicalDevice::new(&instance, physical_device, &[], &[family_info])?
+    };
+
+    let queues = logical_device.queue_handles_for_family(0);
+    assert2::check!(queues.len() == queue_count as usize);
+    assert2::check!(queues.iter().all(|queue| *queue != vk::Queue::default()));
+    assert2::check!(logical_device.queue_handles_for_family(99).is_empty());
+
+    Ok(())
+}
+
+#[test]
+pub fn enabled_features_should_still_be_reported() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let _validation_guard = instance.validation_error_guard();
+
+    let physical_device = PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?
+    .into_iter()
+    .next()
+    .unwrap();
+    let mut features = PhysicalDeviceFeatures::default();
+    features.features_mut().sampler_anisotropy =
+        physical_device.features().features().sampler_anisotropy;
+    let physical_device = physical_device.with_features(features);
+
+    let family_info = QueueFamilyInfo::from_priorities(0, [1.0])?;
+    let logical_device = unsafe {
+        LogicalDevice::new(&instance, physical_device, &[], &[family_info])?
+    };
+
+    logical_device.verify_enabled_features(&instance)?;
+
+    Ok(())
+}
+
+#[test]
+pub fn create_compute_transfer_device() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let _validation_guard = instance.validation_error_guard();
+
+    let (logical_device, compute_queue, transfer_queue) = unsafe {
+        LogicalDevice::new_compute_transfer(
+            &instance,
+            &PhysicalDeviceFeatures::default(),
+        )?
+    };
+
+    log::info!(
+        "Created compute device {} with queues {:?} and {:?}",
+        logical_device,
+        compute_queue,
+        transfer_queue
+    );
+    assert2::check!(compute_queue != vk::Queue::default());
+    assert2::check!(transfer_queue != vk::Queue::default());
+
+    Ok(())
+}
+
+#[test]
+pub fn frames_in_flight_should_default_to_two() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let _validation_guard = instance.validation_error_guard();
+
+    let (mut logical_device, _queues) = unsafe {
+        DeviceBuilder::new(&instance)
+            .queue(vk::QueueFlags::COMPUTE)
+            .build()?
+    };
+    assert2::check!(logical_device.frames_in_flight() == 2);
+
+    logical_device.set_frames_in_flight(0);
+    assert2::check!(logical_device.frames_in_flight() == 1);
+
+    let (logical_device, _queues) = unsafe {
+        DeviceBuilder::new(&instance)
+            .queue(vk::QueueFlags::COMPUTE)
+            .frames_in_flight(3)
+            .build()?
+    };
+    assert2::check!(logical_device.frames_in_flight() == 3);
+
+    Ok(())
+}
+
+#[test]
+pub fn create_device_with_all_supported_features() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let _validation_guard = instance.validation_error_guard();
+
+    let physical_device = PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?
+    .into_iter()
+    .find(|device| {
+        device
+            .queue_families_with_flags(vk::QueueFlags::COMPUTE)
+            .next()
+            .is_some()
+    })
+    .unwrap();
+    let (compute_family_index, _) = physical_device
+        .queue_families_with_flags(vk::QueueFlags::COMPUTE)
+        .next()
+        .unwrap();
+
+    let all_features = physical_device.all_supported_features();
+    let physical_device = physical_device.with_features(all_features);
+
+    let mut family_info = QueueFamilyInfo::new(compute_family_index);
+    family_info.add_queue_priority(1.0);
+
+    let logical_device = unsafe {
+        LogicalDevice::new(&instance, physical_device, &[], &[family_info])?
+    };
+
+    for extension in all_features.required_extensions() {
+        let name = extension.to_str()?;
+        assert2::check!(logical_device
+            .active_physical_device_extensions()
+            .iter()
+            .any(|active| active == name));
+    }
+    logical_device.verify_enabled_features(&instance)?;
+
+    Ok(())
+}
+
+#[test]
+pub fn global_priority_should_be_chained_into_the_create_info() {
+    let mut family_info = QueueFamilyInfo::new(0);
+    family_info.add_queue_priority(1.0);
+    family_info.set_global_priority(vk::QueueGlobalPriorityKHR::HIGH);
+
+    let create_info = unsafe { family_info.as_queue_create_info() };
+    assert2::assert!(!create_info.p_next.is_null());
+
+    let global_priority = unsafe {
+        // SAFE because family_info is still alive and set_global_priority
+        // always chains a DeviceQueueGlobalPriorityCreateInfoKHR.
+        &*(create_info.p_next
+            as *const vk::DeviceQueueGlobalPriorityCreateInfoKHR)
+    };
+    assert2::check!(
+        global_priority.s_type
+            == vk::StructureType::DEVICE_QUEUE_GLOBAL_PRIORITY_CREATE_INFO_KHR
+    );
+    assert2::check!(
+        global_priority.global_priority == vk::QueueGlobalPriorityKHR::HIGH
+    );
+    assert2::check!(global_priority.p_next.is_null());
+}
+
+#[test]
+pub fn create_device_on_a_vulkan_10_instance() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe {
+        VulkanInstance::new_with_application_info(
+            vk::ApplicationInfo {
+                api_version: vk::API_VERSION_1_0,
+                ..Default::default()
+            },
+            &[],
+            &[],
+        )?
+    };
+    let _validation_guard = instance.validation_error_guard();
+
+    let physical_device = PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?
+    .into_iter()
+    .next()
+    .unwrap();
+
+    // Request a core 1.0 feature when the device has it, so the features are
+    // actually passed through p_enabled_features.
+    let mut features = PhysicalDeviceFeatures::default();
+    features.features_mut().sampler_anisotropy =
+        physical_device.features().features().sampler_anisotropy;
+    let physical_device = physical_device.with_features(features);
+
+    let family_info = QueueFamilyInfo::from_priorities(0, [1.0])?;
+    let logical_device = unsafe {
+        LogicalDevice::new(&instance, physical_device, &[], &[family_info])?
+    };
+    assert2::check!(
+        logical_device
+            .enabled_features()
+            .features()
+            .sampler_anisotropy
+            == features.features().sampler_anisotropy
+    );
+    logical_device.verify_enabled_features(&instance)?;
+
+    Ok(())
+}
+
+#[test]
+pub fn create_device_on_a_vulkan_12_instance() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe {
+        VulkanInstance::new_with_application_info(
+            vk::ApplicationInfo {
+                api_version: vk::API_VERSION_1_2,
+                ..Default::default()
+            },
+            &[],
+            &[],
+        )?
+    };
+    let _validation_guard = instance.validation_error_guard();
+
+    // The Vulkan 1.3 features struct must not be chained into the create
+    // info, even when the device itself supports Vulkan 1.3.
+    let physical_device = PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?
+    .into_iter()
+    .next()
+    .unwrap();
+
+    let family_info = QueueFamilyInfo::from_priorities(0, [1.0])?;
+    let logical_device = unsafe {
+        LogicalDevice::new(&instance, physical_device, &[], &[family_info])?
+    };
+    logical_device.verify_enabled_features(&instance)?;
+
+    Ok(())
+}
+
+#[test]
+pub fn create_device_from_a_single_device_group() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let _validation_guard = instance.validation_error_guard();
+
+    let groups = PhysicalDevice::enumerate_device_groups(&instance)?;
+    let group = match groups
+        .into_iter()
+        .find(|group| group.physical_devices().len() == 1)
+    {
+        Some(group) => group,
+        None => return Ok(()),
+    };
+    let physical_device = group.physical_devices()[0].clone();
+
+    let family_info = QueueFamilyInfo::from_priorities(0, [1.0])?;
+    let logical_device = unsafe {
+        LogicalDevice::new_with_device_group(
+            &instance,
+            &group,
+            physical_device.clone(),
+            &[],
+            &[family_info],
+        )?
+    };
+    let (created_with, requested) = unsafe {
+        (
+            *logical_device.physical_device().raw(),
+            *physical_device.raw(),
+        )
+    };
+    assert2::check!(created_with == requested);
+
+    Ok(())
+}
+
+#[test]
+pub fn devices_outside_of_the_group_should_be_rejected() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    // A device can only be outside of a group when there are at least two.
+    let groups = PhysicalDevice::enumerate_device_groups(&instance)?;
+    if groups.len() < 2 {
+        return Ok(());
+    }
+    let outsider = groups[1].physical_devices()[0].clone();
+
+    let family_info = QueueFamilyInfo::from_priorities(0, [1.0])?;
+    let_assert!(
+        Err(InstanceError::PhysicalDeviceNotInGroup) = unsafe {
+            LogicalDevice::new_with_device_group(
+                &instance,
+                &groups[0],
+                outsider,
+                &[],
+                &[family_info],
+            )
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn device_groups_should_require_vulkan_11() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe {
+        VulkanInstance::new_with_application_info(
+            vk::ApplicationInfo {
+                api_version: vk::API_VERSION_1_0,
+                ..Default::default()
+            },
+            &[],
+            &[],
+        )?
+    };
+
+    // Every device gets its own group below Vulkan 1.1.
+    let groups = PhysicalDevice::enumerate_device_groups(&instance)?;
+    assert2::check!(
+        groups.len() == PhysicalDevice::enumerate_all(&instance)?.len()
+    );
+    for group in &groups {
+        assert2::check!(group.physical_devices().len() == 1);
+    }
+
+    let group = &groups[0];
+    let family_info = QueueFamilyInfo::from_priorities(0, [1.0])?;
+    let_assert!(
+        Err(InstanceError::DeviceGroupsNotSupported) = unsafe {
+            LogicalDevice::new_with_device_group(
+                &instance,
+                group,
+                group.physical_devices()[0].clone(),
+                &[],
+                &[family_info],
+            )
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn non_core_features_should_be_rejected_on_a_vulkan_10_instance(
+) -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe {
+        VulkanInstance::new_with_application_info(
+            vk::ApplicationInfo {
+                api_version: vk::API_VERSION_1_0,
+                ..Default::default()
+            },
+            &[],
+            &[],
+        )?
+    };
+
+    let mut features = PhysicalDeviceFeatures::default();
+    features.vulkan_13_features_mut().dynamic_rendering = vk::TRUE;
+    let physical_device = PhysicalDevice::enumerate_all(&instance)?
+        .into_iter()
+        .next()
+        .unwrap()
+        .with_features(features);
+
+    let family_info = QueueFamilyInfo::from_priorities(0, [1.0])?;
+    let_assert!(
+        Err(InstanceError::MissingDeviceFeatures(missing)) = unsafe {
+            LogicalDevice::new(&instance, physical_device, &[], &[family_info])
+        }
+    );
+    assert2::check!(missing == vec!["dynamic_rendering".to_owned()]);
+
+    Ok(())
+}
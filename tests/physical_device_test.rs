@@ -5,8 +5,11 @@ mod common;
 use {
     anyhow::Result,
     ash::vk,
-    assert2::assert,
-    ccthw_ash_instance::{PhysicalDevice, PhysicalDeviceFeatures},
+    assert2::{assert, let_assert},
+    ccthw_ash_instance::{
+        DeviceRequirements, PhysicalDevice, PhysicalDeviceFeatures,
+        PhysicalDeviceProperties, RejectReason, VulkanHandle,
+    },
 };
 
 #[test]
@@ -30,6 +33,679 @@ pub fn get_physical_device_with_features() -> Result<()> {
     Ok(())
 }
 
+#[test]
+pub fn find_dedicated_transfer_family() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )? {
+        if let Some(index) = device.find_dedicated_transfer_family() {
+            let properties = device.queue_family_properties()[index as usize];
+            assert!(properties.queue_flags.contains(vk::QueueFlags::TRANSFER));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn queue_families_with_flags_contain_the_flags() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let flags = vk::QueueFlags::COMPUTE | vk::QueueFlags::TRANSFER;
+    for device in PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )? {
+        for (index, properties) in device.queue_families_with_flags(flags) {
+            assert!(properties.queue_flags.contains(flags));
+            assert!(
+                device.queue_family_properties()[index as usize].queue_flags
+                    == properties.queue_flags
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn extension_version_matches_available_extensions() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        for (name, properties) in device
+            .available_extension_names()
+            .iter()
+            .zip(device.available_extensions())
+        {
+            assert!(
+                device.extension_version(name) == Some(properties.spec_version)
+            );
+        }
+        assert!(device.extension_version("bogus_extension_name").is_none());
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+pub fn json_report_should_describe_the_device() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let report: serde_json::Value =
+            serde_json::from_str(&device.to_json())?;
+        assert!(report["name"] == device.name().as_str());
+        assert!(report["limits"].is_object());
+        assert!(report["features"].is_array());
+        assert!(
+            report["extensions"].as_array().map(|names| names.len())
+                == Some(device.available_extension_names().len())
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn device_requirements_filter_devices() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let all_devices = PhysicalDevice::enumerate_all(&instance)?;
+    let permissive = DeviceRequirements::new();
+    assert!(permissive.filter(all_devices.clone()).len() == all_devices.len());
+
+    let impossible = DeviceRequirements::new()
+        .extension("bogus_extension_name")
+        .queue_flags(vk::QueueFlags::COMPUTE);
+    assert!(impossible.filter(all_devices).is_empty());
+    assert!(impossible.enumerate(&instance)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+pub fn device_requirements_reject_old_api_versions() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let requirements =
+        DeviceRequirements::new().min_api_version(vk::API_VERSION_1_2);
+    for device in requirements.enumerate(&instance)? {
+        assert!(
+            device.properties().properties().api_version >= vk::API_VERSION_1_2
+        );
+    }
+
+    let impossible = DeviceRequirements::new().min_api_version(u32::MAX);
+    assert!(impossible.enumerate(&instance)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+pub fn device_requirements_check_extension_spec_versions() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let name = match device.available_extension_names().first() {
+            Some(name) => name,
+            None => continue,
+        };
+        let spec_version = device.extension_version(name).unwrap();
+
+        let current = DeviceRequirements::new()
+            .extension_with_spec_version(name, spec_version);
+        assert!(current.is_satisfied_by(&device));
+
+        let newer = DeviceRequirements::new()
+            .extension_with_spec_version(name, spec_version + 1);
+        assert!(newer.unmet_requirements(&device).len() == 1);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn graphics_requirements_need_swapchain_support() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in DeviceRequirements::for_graphics().enumerate(&instance)? {
+        assert!(device
+            .available_extension_names()
+            .contains(&"VK_KHR_swapchain".to_owned()));
+        assert!(device
+            .queue_families_with_flags(vk::QueueFlags::GRAPHICS)
+            .next()
+            .is_some());
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn descriptor_buffer_properties_match_extension_support() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let has_extension = device
+            .extension_version(vk::ExtDescriptorBufferFn::name().to_str()?);
+        assert!(
+            device.properties().descriptor_buffer_properties().is_some()
+                == has_extension.is_some()
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn fragment_shading_rates_require_the_extension() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let has_extension = device
+            .extension_version(vk::KhrFragmentShadingRateFn::name().to_str()?)
+            .is_some();
+        assert!(
+            device.features().fragment_shading_rate_features().is_some()
+                == has_extension
+        );
+        let rates = device.fragment_shading_rates(&instance);
+        if has_extension {
+            // the 1x1 rate is always supported
+            assert!(!rates?.is_empty());
+        } else {
+            assert!(rates.is_err());
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn with_features_should_replace_requested_features() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let mut features = PhysicalDeviceFeatures::default();
+        features.features_mut().robust_buffer_access = vk::TRUE;
+
+        let copy = device.with_features(features);
+        assert!(
+            copy.requested_features().features().robust_buffer_access
+                == vk::TRUE
+        );
+        assert!(copy.name() == device.name());
+        assert!(
+            device.requested_features().features().robust_buffer_access
+                == vk::FALSE
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn platform_presentation_needs_a_surface_extension() -> Result<()> {
+    common::setup_logger();
+
+    // no surface extensions are enabled
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        for index in 0..device.queue_family_properties().len() as u32 {
+            let supported =
+                device.queue_family_can_present_platform(&instance, index);
+            if cfg!(target_os = "android") {
+                assert!(supported == Some(true));
+            } else {
+                assert!(supported.is_none());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn multiview_is_reported_on_vulkan_11_devices() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let multiview = PhysicalDeviceFeatures::default().with_multiview();
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let is_vulkan_11 =
+            device.properties().properties().api_version >= vk::API_VERSION_1_1;
+        assert!(
+            device.features().multiview_features().is_some() == is_vulkan_11
+        );
+        if !is_vulkan_11 {
+            assert!(!multiview.is_supported_by(device.features()));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn decode_driver_versions_by_vendor() {
+    let mut properties = PhysicalDeviceProperties::default();
+
+    properties.properties_mut().vendor_id = 0x10DE;
+    properties.properties_mut().driver_version =
+        (535 << 22) | (104 << 14) | (5 << 6);
+    assert!(properties.driver_version_string() == "535.104.5.0");
+
+    properties.properties_mut().vendor_id = 0x1002;
+    properties.properties_mut().driver_version =
+        vk::make_api_version(0, 2, 0, 279);
+    assert!(properties.driver_version_string() == "2.0.279");
+}
+
+#[test]
+pub fn extension_features_require_their_extensions() {
+    let mut features = PhysicalDeviceFeatures::default();
+    assert!(features.required_extensions().is_empty());
+
+    // core features don't need an extension
+    features = features.with_multiview();
+    assert!(features.required_extensions().is_empty());
+
+    features.robustness2_features_mut().null_descriptor = vk::TRUE;
+    features.robustness2_features_mut().robust_image_access2 = vk::TRUE;
+    assert!(
+        features.required_extensions() == vec![vk::ExtRobustness2Fn::name()]
+    );
+}
+
+#[test]
+pub fn null_descriptor_preset_should_enable_robustness2() {
+    let features = PhysicalDeviceFeatures::default().with_null_descriptor();
+
+    assert!(
+        features
+            .robustness2_features()
+            .map(|robustness2| robustness2.null_descriptor)
+            == Some(vk::TRUE)
+    );
+    assert!(features
+        .required_extensions()
+        .contains(&vk::ExtRobustness2Fn::name()));
+}
+
+#[test]
+pub fn sparse_binding_family_supports_sparse_binding() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        log::info!("{:#?}", device.sparse_properties());
+        if let Some(index) = device.find_sparse_binding_family() {
+            let properties = &device.queue_family_properties()[index as usize];
+            assert!(properties
+                .queue_flags
+                .contains(vk::QueueFlags::SPARSE_BINDING));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn device_info_can_be_sent_between_threads() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let info = device.info();
+        let name = std::thread::spawn(move || info.name().to_owned())
+            .join()
+            .unwrap();
+        assert!(name == device.name());
+        assert!(
+            device.info().extension_names()
+                == device.available_extension_names()
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn shader_objects_require_the_extension() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let mut shader_objects = PhysicalDeviceFeatures::default();
+    shader_objects.shader_object_features_mut().shader_object = vk::TRUE;
+
+    for device in
+        PhysicalDevice::enumerate_supported_devices(&instance, &shader_objects)?
+    {
+        assert!(device
+            .extension_version(vk::ExtShaderObjectFn::name().to_str()?)
+            .is_some());
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn float32_atomic_add_requires_the_extension() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let atomics = PhysicalDeviceFeatures::default().with_float32_atomic_add();
+    assert!(
+        atomics.required_extensions()
+            == vec![vk::ExtShaderAtomicFloatFn::name()]
+    );
+
+    for device in
+        PhysicalDevice::enumerate_supported_devices(&instance, &atomics)?
+    {
+        let atomic_float = device.features().shader_atomic_float_features();
+        let_assert!(Some(atomic_float) = atomic_float);
+        assert!(atomic_float.shader_buffer_float32_atomic_add == vk::TRUE);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn cooperative_matrix_properties_require_the_extension() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let has_extension = device
+            .extension_version(vk::NvCooperativeMatrixFn::name().to_str()?)
+            .is_some();
+        let properties = device.cooperative_matrix_properties(&instance);
+        assert!(properties.is_ok() == has_extension);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn any_device_supports_matches_enumeration() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let mut features = PhysicalDeviceFeatures::default();
+    features.features_mut().geometry_shader = vk::TRUE;
+
+    let supported_count =
+        PhysicalDevice::count_supported_devices(&instance, &features)?;
+    assert!(instance.any_device_supports(&features)? == (supported_count > 0));
+    assert!(instance.any_device_supports(&PhysicalDeviceFeatures::default())?);
+
+    Ok(())
+}
+
+#[test]
+pub fn alignment_limits_are_powers_of_two() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let properties = device.properties();
+        assert!(properties
+            .min_uniform_buffer_offset_alignment()
+            .is_power_of_two());
+        assert!(properties
+            .min_storage_buffer_offset_alignment()
+            .is_power_of_two());
+        assert!(properties.non_coherent_atom_size().is_power_of_two());
+        assert!(
+            properties.optimal_buffer_copy_offset_alignment()
+                == properties
+                    .properties()
+                    .limits
+                    .optimal_buffer_copy_offset_alignment
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn merge_feature_requirements() {
+    let mut renderer = PhysicalDeviceFeatures::default();
+    renderer.features_mut().sampler_anisotropy = vk::TRUE;
+    renderer.vulkan_13_features_mut().dynamic_rendering = vk::TRUE;
+
+    let physics = PhysicalDeviceFeatures::default().with_float32_atomic_add();
+
+    let mut merged = renderer;
+    merged.merge(&physics);
+
+    assert!(renderer.is_supported_by(&merged));
+    assert!(physics.is_supported_by(&merged));
+    assert!(!merged.is_supported_by(&renderer));
+}
+
+#[test]
+pub fn present_wait_requires_its_prerequisite_extensions() {
+    let mut features = PhysicalDeviceFeatures::default();
+    features.present_id_features_mut().present_id = vk::TRUE;
+    features.present_wait_features_mut().present_wait = vk::TRUE;
+
+    let extensions = features.required_extensions();
+    assert!(
+        extensions
+            == vec![
+                vk::KhrPresentIdFn::name(),
+                vk::KhrSwapchainFn::name(),
+                vk::KhrPresentWaitFn::name(),
+            ]
+    );
+}
+
+#[test]
+pub fn best_effort_devices_get_supported_optional_features() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let required = PhysicalDeviceFeatures::default();
+    let mut optional =
+        PhysicalDeviceFeatures::default().with_modern_rendering();
+    optional.features_mut().sampler_anisotropy = vk::TRUE;
+
+    for (device, granted) in
+        PhysicalDevice::enumerate_best_effort(&instance, &required, &optional)?
+    {
+        assert!(granted.is_supported_by(device.features()));
+        assert!(
+            (granted.features().sampler_anisotropy == vk::TRUE)
+                == (device.features().features().sampler_anisotropy
+                    == vk::TRUE)
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn vulkan_13_devices_report_inline_uniform_block_limits() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        if device.properties().properties().api_version >= vk::API_VERSION_1_3 {
+            assert!(device
+                .properties()
+                .inline_uniform_block_properties()
+                .is_some());
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn wrap_raw_physical_device() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for handle in unsafe { instance.ash().enumerate_physical_devices()? } {
+        let device = PhysicalDevice::from_raw(
+            &instance,
+            handle,
+            &PhysicalDeviceFeatures::default(),
+        )?;
+        assert!(unsafe { *device.raw() } == handle);
+        log::info!("Wrapped raw device {}", device);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn every_device_has_at_least_zero_bytes_of_device_local_memory(
+) -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )? {
+        assert!(device.has_device_local_memory_at_least(0));
+        assert!(!device.has_device_local_memory_at_least(u64::MAX));
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn max_usable_sample_count_is_a_single_supported_count() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )? {
+        let count = device.max_usable_sample_count();
+        let limits = &device.properties().properties().limits;
+        assert!(count.as_raw().count_ones() == 1);
+        assert!(limits.framebuffer_color_sample_counts.contains(count));
+        assert!(limits.framebuffer_depth_sample_counts.contains(count));
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn count_supported_devices_matches_enumeration() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let features = PhysicalDeviceFeatures::default();
+
+    let count = PhysicalDevice::count_supported_devices(&instance, &features)?;
+    let devices =
+        PhysicalDevice::enumerate_supported_devices(&instance, &features)?;
+
+    assert!(count == devices.len());
+
+    Ok(())
+}
+
+#[test]
+pub fn enumerated_devices_store_available_features() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let requested = PhysicalDeviceFeatures::default();
+
+    for device in
+        PhysicalDevice::enumerate_supported_devices(&instance, &requested)?
+    {
+        assert!(device
+            .requested_features()
+            .is_supported_by(device.features()));
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn enumerate_all_devices() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let all_devices = PhysicalDevice::enumerate_all(&instance)?;
+    let supported_count = PhysicalDevice::count_supported_devices(
+        &instance,
+        &PhysicalDeviceFeatures::default(),
+    )?;
+
+    assert!(all_devices.len() >= supported_count);
+    for device in all_devices {
+        log::info!("Found device {}", device);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn every_device_belongs_to_a_group() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    let groups = PhysicalDevice::enumerate_device_groups(&instance)?;
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        assert!(groups.iter().any(|group| group.contains(&device)));
+    }
+
+    Ok(())
+}
+
 #[test]
 pub fn feature_should_not_be_supported_by_default() {
     common::setup_logger();
@@ -71,6 +747,60 @@ pub fn features_should_be_supported_when_explicitly_enabled() {
     assert!(desired_features.is_supported_by(&available_features));
 }
 
+#[test]
+pub fn modern_rendering_should_require_vulkan_13_features() {
+    common::setup_logger();
+
+    let desired_features =
+        PhysicalDeviceFeatures::default().with_modern_rendering();
+    assert!(
+        desired_features.vulkan_13_features().dynamic_rendering == vk::TRUE
+    );
+    assert!(
+        !desired_features.is_supported_by(&PhysicalDeviceFeatures::default())
+    );
+    assert!(desired_features.is_supported_by(&desired_features));
+}
+
+#[test]
+pub fn full_descriptor_indexing_should_enable_bindless_features() {
+    common::setup_logger();
+
+    let desired_features =
+        PhysicalDeviceFeatures::default().with_full_descriptor_indexing();
+
+    let indexing = desired_features.descriptor_indexing_features();
+    assert!(indexing.runtime_descriptor_array == vk::TRUE);
+    assert!(indexing.descriptor_binding_partially_bound == vk::TRUE);
+    assert!(
+        !desired_features.is_supported_by(&PhysicalDeviceFeatures::default())
+    );
+}
+
+#[test]
+pub fn optional_features_should_not_be_supported_when_struct_is_missing() {
+    common::setup_logger();
+
+    let desired_features =
+        PhysicalDeviceFeatures::default().with_small_storage();
+
+    assert!(
+        !desired_features.is_supported_by(&PhysicalDeviceFeatures::default())
+    );
+}
+
+#[test]
+pub fn optional_features_should_be_supported_when_available() {
+    common::setup_logger();
+
+    let desired_features =
+        PhysicalDeviceFeatures::default().with_small_storage();
+    let available_features =
+        PhysicalDeviceFeatures::default().with_small_storage();
+
+    assert!(desired_features.is_supported_by(&available_features));
+}
+
 #[test]
 pub fn send_physical_device() -> Result<()> {
     common::setup_logger();
@@ -93,3 +823,547 @@ pub fn send_physical_device() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+pub fn missing_features_should_name_unsupported_features() {
+    common::setup_logger();
+
+    let mut desired_features = PhysicalDeviceFeatures::default();
+    desired_features.features_mut().geometry_shader = vk::TRUE;
+    desired_features.vulkan_13_features_mut().synchronization2 = vk::TRUE;
+    let mut available_features = PhysicalDeviceFeatures::default();
+    available_features.features_mut().geometry_shader = vk::TRUE;
+
+    assert!(
+        desired_features.missing_features(&available_features)
+            == vec!["synchronization2"]
+    );
+    assert!(desired_features
+        .missing_features(&desired_features)
+        .is_empty());
+}
+
+#[test]
+pub fn rejected_devices_should_be_reported() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    let mut features = PhysicalDeviceFeatures::default();
+    features.features_mut().sparse_residency_aliased = vk::TRUE;
+
+    let mut rejected = vec![];
+    let supported =
+        PhysicalDevice::enumerate_supported_devices_with_rejections(
+            &instance,
+            &features,
+            &mut |physical_device, reason| {
+                log::info!("Rejected {:?} because {}", physical_device, reason);
+                rejected.push(reason);
+            },
+        )?;
+
+    let all_devices = PhysicalDevice::enumerate_all(&instance)?;
+    assert!(supported.len() + rejected.len() == all_devices.len());
+    for reason in rejected {
+        assert!(
+            reason
+                == RejectReason::MissingFeatures(vec![
+                    "sparse_residency_aliased"
+                ])
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn memory_totals_should_not_exceed_all_heaps() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let memory_properties = device.memory_properties();
+        let total_bytes: u64 = memory_properties.memory_heaps
+            [..memory_properties.memory_heap_count as usize]
+            .iter()
+            .map(|heap| heap.size)
+            .sum();
+        log::info!(
+            "{} has {} bytes of VRAM",
+            device.name(),
+            device.device_local_memory_bytes()
+        );
+        assert!(device.device_local_memory_bytes() <= total_bytes);
+        assert!(device.host_visible_memory_bytes() <= total_bytes);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn extra_p_next_should_be_linked_at_the_tail() {
+    common::setup_logger();
+
+    let mut extra = vk::PhysicalDeviceIndexTypeUint8FeaturesEXT {
+        index_type_uint8: vk::TRUE,
+        ..Default::default()
+    };
+    let mut features = PhysicalDeviceFeatures::default();
+    let mut tail = std::ptr::null_mut();
+    unsafe {
+        features.set_extra_p_next(&mut extra as *mut _ as *mut _);
+        let mut next =
+            features.link_p_next_chain().p_next as *mut vk::BaseOutStructure;
+        while !next.is_null() {
+            tail = next;
+            next = (*next).p_next;
+        }
+    }
+
+    assert!(tail == &mut extra as *mut _ as *mut vk::BaseOutStructure);
+}
+
+#[test]
+pub fn cpu_devices_should_be_software_rasterizers() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        log::info!(
+            "{} is a software rasterizer? {}",
+            device.name(),
+            device.is_software_rasterizer()
+        );
+        if device.properties().properties().device_type
+            == vk::PhysicalDeviceType::CPU
+        {
+            assert!(device.is_software_rasterizer());
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn vertex_attribute_divisor_should_be_checked() -> Result<()> {
+    common::setup_logger();
+
+    let mut desired_features = PhysicalDeviceFeatures::default();
+    desired_features
+        .vertex_attribute_divisor_features_mut()
+        .vertex_attribute_instance_rate_divisor = vk::TRUE;
+    assert!(
+        !desired_features.is_supported_by(&PhysicalDeviceFeatures::default())
+    );
+    assert!(
+        desired_features.required_extensions()
+            == vec![vk::ExtVertexAttributeDivisorFn::name()]
+    );
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let has_extension = device
+            .extension_version("VK_EXT_vertex_attribute_divisor")
+            .is_some();
+        let max_divisor = device.properties().max_vertex_attrib_divisor();
+        assert!(max_divisor.is_none() || has_extension);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn extended_dynamic_state_versions_are_independent() {
+    common::setup_logger();
+
+    let mut desired_features = PhysicalDeviceFeatures::default();
+    desired_features
+        .extended_dynamic_state_features_mut()
+        .extended_dynamic_state = vk::TRUE;
+    desired_features
+        .extended_dynamic_state3_features_mut()
+        .extended_dynamic_state3_polygon_mode = vk::TRUE;
+
+    let mut available_features = PhysicalDeviceFeatures::default();
+    available_features
+        .extended_dynamic_state_features_mut()
+        .extended_dynamic_state = vk::TRUE;
+    assert!(!desired_features.is_supported_by(&available_features));
+
+    available_features
+        .extended_dynamic_state3_features_mut()
+        .extended_dynamic_state3_polygon_mode = vk::TRUE;
+    assert!(desired_features.is_supported_by(&available_features));
+    assert!(available_features
+        .extended_dynamic_state2_features()
+        .is_none());
+    assert!(
+        desired_features.required_extensions()
+            == vec![
+                vk::ExtExtendedDynamicStateFn::name(),
+                vk::ExtExtendedDynamicState3Fn::name(),
+            ]
+    );
+}
+
+#[test]
+pub fn feature_diff_should_categorize_features() {
+    common::setup_logger();
+
+    let mut requested = PhysicalDeviceFeatures::default();
+    requested.features_mut().geometry_shader = vk::TRUE;
+    requested.features_mut().sampler_anisotropy = vk::TRUE;
+
+    let mut available = PhysicalDeviceFeatures::default();
+    available.features_mut().sampler_anisotropy = vk::TRUE;
+    available.vulkan_13_features_mut().dynamic_rendering = vk::TRUE;
+
+    let diff = requested.diff(&available);
+    assert!(diff.requested_and_available() == ["sampler_anisotropy"]);
+    assert!(diff.requested_but_missing() == ["geometry_shader"]);
+    assert!(diff.available_but_not_requested() == ["dynamic_rendering"]);
+    assert!(
+        requested.missing_features(&available) == diff.requested_but_missing()
+    );
+}
+
+#[test]
+pub fn queue_families_equal_compares_indices() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        assert!(device.queue_families_equal(0, 0));
+        assert!(!device.queue_families_equal(0, 1));
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn porting_features_should_be_checked() {
+    common::setup_logger();
+
+    let mut desired_features = PhysicalDeviceFeatures::default();
+    desired_features
+        .provoking_vertex_features_mut()
+        .provoking_vertex_last = vk::TRUE;
+    desired_features
+        .depth_clip_control_features_mut()
+        .depth_clip_control = vk::TRUE;
+
+    let mut available_features = desired_features;
+    available_features
+        .depth_clip_control_features_mut()
+        .depth_clip_control = vk::FALSE;
+
+    assert!(
+        desired_features.missing_features(&available_features)
+            == vec!["depth_clip_control"]
+    );
+    assert!(
+        desired_features.required_extensions()
+            == vec![
+                vk::ExtProvokingVertexFn::name(),
+                vk::ExtDepthClipControlFn::name(),
+            ]
+    );
+}
+
+#[test]
+pub fn summary_should_include_name_and_queue_families() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let summary = device.summary();
+        log::info!("{}", summary);
+        assert!(summary.starts_with(&device.name()));
+        assert!(summary.contains("Queue family 0"));
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn query_active_tools() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        for tool in device.tool_properties(&instance)? {
+            let name = unsafe { std::ffi::CStr::from_ptr(tool.name.as_ptr()) };
+            log::info!("{} has active tool {:?}", device.name(), name);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn query_queue_family_global_priorities() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let family_count = device.queue_family_properties().len() as u32;
+        for index in 0..family_count {
+            if let Some(priorities) =
+                device.queue_family_global_priorities(index)
+            {
+                log::info!(
+                    "{} family {} supports {:?}",
+                    device.name(),
+                    index,
+                    priorities
+                );
+                assert!(!priorities.is_empty());
+            }
+        }
+        assert!(device
+            .queue_family_global_priorities(family_count)
+            .is_none());
+        assert!(!device.queue_family_supports_global_priority(
+            family_count,
+            vk::QueueGlobalPriorityKHR::MEDIUM
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn timestamp_support_matches_device_properties() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let limits = &device.properties().properties().limits;
+        assert!(device.timestamp_period() == limits.timestamp_period);
+
+        let families = device.queue_family_properties();
+        for (index, properties) in families.iter().enumerate() {
+            assert!(
+                device.queue_family_supports_timestamps(index as u32)
+                    == (properties.timestamp_valid_bits > 0)
+            );
+        }
+        let unknown_family = families.len() as u32;
+        assert!(!device.queue_family_supports_timestamps(unknown_family));
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn supported_external_handle_types_are_compatible_with_themselves(
+) -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe { VulkanInstance::new(&[], &[])? };
+    if instance.api_version() < vk::API_VERSION_1_1 {
+        return Ok(());
+    }
+
+    let (memory_handle_type, semaphore_handle_type) =
+        if cfg!(target_os = "windows") {
+            (
+                vk::ExternalMemoryHandleTypeFlags::OPAQUE_WIN32,
+                vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_WIN32,
+            )
+        } else {
+            (
+                vk::ExternalMemoryHandleTypeFlags::OPAQUE_FD,
+                vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD,
+            )
+        };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        if device.properties().properties().api_version < vk::API_VERSION_1_1 {
+            continue;
+        }
+
+        let buffer_properties = device.external_buffer_properties(
+            &instance,
+            &vk::PhysicalDeviceExternalBufferInfo {
+                usage: vk::BufferUsageFlags::STORAGE_BUFFER,
+                handle_type: memory_handle_type,
+                ..Default::default()
+            },
+        );
+        let memory_properties =
+            buffer_properties.unwrap().external_memory_properties;
+        if !memory_properties.external_memory_features.is_empty() {
+            assert!(memory_properties
+                .compatible_handle_types
+                .contains(memory_handle_type));
+        }
+
+        let semaphore_properties = device.external_semaphore_properties(
+            &instance,
+            &vk::PhysicalDeviceExternalSemaphoreInfo {
+                handle_type: semaphore_handle_type,
+                ..Default::default()
+            },
+        );
+        let semaphore_properties = semaphore_properties.unwrap();
+        if !semaphore_properties.external_semaphore_features.is_empty() {
+            assert!(semaphore_properties
+                .compatible_handle_types
+                .contains(semaphore_handle_type));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn external_properties_are_not_queried_on_a_vulkan_10_instance(
+) -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe {
+        VulkanInstance::new_with_application_info(
+            vk::ApplicationInfo {
+                api_version: vk::API_VERSION_1_0,
+                ..Default::default()
+            },
+            &[],
+            &[],
+        )?
+    };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        assert!(device
+            .external_buffer_properties(
+                &instance,
+                &vk::PhysicalDeviceExternalBufferInfo {
+                    usage: vk::BufferUsageFlags::STORAGE_BUFFER,
+                    handle_type: vk::ExternalMemoryHandleTypeFlags::OPAQUE_FD,
+                    ..Default::default()
+                },
+            )
+            .is_none());
+        assert!(device
+            .external_semaphore_properties(
+                &instance,
+                &vk::PhysicalDeviceExternalSemaphoreInfo {
+                    handle_type:
+                        vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD,
+                    ..Default::default()
+                },
+            )
+            .is_none());
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn missing_host_query_reset_should_be_reported() {
+    common::setup_logger();
+
+    let desired_features =
+        PhysicalDeviceFeatures::default().with_host_query_reset();
+
+    assert!(
+        desired_features.missing_features(&PhysicalDeviceFeatures::default())
+            == vec!["host_query_reset"]
+    );
+    assert!(desired_features
+        .missing_features(&desired_features)
+        .is_empty());
+}
+
+#[test]
+pub fn device_requirement_features_should_accumulate() {
+    common::setup_logger();
+
+    let mut anisotropy = PhysicalDeviceFeatures::default();
+    anisotropy.features_mut().sampler_anisotropy = vk::TRUE;
+    let mut geometry = PhysicalDeviceFeatures::default();
+    geometry.features_mut().geometry_shader = vk::TRUE;
+
+    let requirements = DeviceRequirements::new()
+        .features(anisotropy)
+        .features(geometry);
+    let required = requirements.required_features();
+    assert!(anisotropy.is_supported_by(required));
+    assert!(geometry.is_supported_by(required));
+
+    let graphics = DeviceRequirements::for_graphics().features(geometry);
+    let required = graphics.required_features();
+    assert!(required.features().sampler_anisotropy == vk::TRUE);
+    assert!(required.features().geometry_shader == vk::TRUE);
+}
+
+#[test]
+pub fn conditional_rendering_should_be_checked() {
+    common::setup_logger();
+
+    let mut desired_features = PhysicalDeviceFeatures::default();
+    desired_features
+        .conditional_rendering_features_mut()
+        .conditional_rendering = vk::TRUE;
+    assert!(
+        !desired_features.is_supported_by(&PhysicalDeviceFeatures::default())
+    );
+
+    let mut available_features = desired_features;
+    assert!(desired_features.is_supported_by(&available_features));
+
+    available_features
+        .conditional_rendering_features_mut()
+        .conditional_rendering = vk::FALSE;
+    available_features
+        .conditional_rendering_features_mut()
+        .inherited_conditional_rendering = vk::TRUE;
+    assert!(!desired_features.is_supported_by(&available_features));
+
+    assert!(
+        desired_features.required_extensions()
+            == vec![vk::ExtConditionalRenderingFn::name()]
+    );
+}
+
+#[test]
+pub fn features_are_gated_on_the_instance_api_version() -> Result<()> {
+    common::setup_logger();
+
+    let instance = unsafe {
+        VulkanInstance::new_with_application_info(
+            vk::ApplicationInfo {
+                api_version: vk::API_VERSION_1_1,
+                ..Default::default()
+            },
+            &[],
+            &[],
+        )?
+    };
+
+    for device in PhysicalDevice::enumerate_all(&instance)? {
+        let mut features = *device.features();
+        assert!(features.storage_8bit_features().is_none());
+        assert!(features.host_query_reset_features().is_none());
+
+        // Walk the chain to check that the Vulkan 1.3 struct is left out.
+        let mut p_next = unsafe { features.link_p_next_chain() }.p_next;
+        while !p_next.is_null() {
+            let header = unsafe { &*(p_next as *const vk::BaseOutStructure) };
+            assert!(
+                header.s_type
+                    != vk::StructureType::PHYSICAL_DEVICE_VULKAN_1_3_FEATURES
+            );
+            p_next = header.p_next as *mut std::ffi::c_void;
+        }
+    }
+
+    Ok(())
+}